@@ -0,0 +1,15 @@
+//! Reports if the ICU4X data is replaced with generated data, so that builds
+//! with a subset of the data can check that it was generated for them (see
+//! `src/data.rs`).
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=ICU4X_DATA_DIR");
+    println!("cargo:rustc-check-cfg=cfg(icu4x_custom_data)");
+
+    // The compiled data of the ICU4X crates has all locales and time zone
+    // names. It's only replaced if `ICU4X_DATA_DIR` points to data generated
+    // with `icu4x-datagen` (the ICU4X data crates use the same check).
+    if std::env::var_os("ICU4X_DATA_DIR").is_some() {
+        println!("cargo:rustc-cfg=icu4x_custom_data");
+    }
+}
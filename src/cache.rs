@@ -0,0 +1,240 @@
+//! Caches for formatters across plugin calls.
+//!
+//! Typst calls the plugin with the same locale and options many times, so
+//! creating the formatters (which loads their data) dominates the runtime.
+//! The caches are bounded and evict the least recently used entry.
+
+use std::{any::Any, cell::RefCell, thread::LocalKey};
+
+#[cfg(not(feature = "non-gregorian"))]
+use icu_calendar::cal::Gregorian;
+use icu_calendar::Iso;
+#[cfg(feature = "non-gregorian")]
+use icu_datetime::DateTimeFormatter;
+#[cfg(not(feature = "non-gregorian"))]
+use icu_datetime::FixedCalendarDateTimeFormatter;
+use icu_datetime::{
+    fieldsets::enums::CompositeFieldSet, DateTimeFormatterPreferences, FormattedDateTime,
+};
+use icu_time::{zone::models::AtTime, TimeZoneInfo, ZonedDateTime};
+use serde::Serialize;
+
+const CAPACITY: usize = 32;
+
+// The keys use the resolved preferences rather than the locale, so locales
+// that only differ in casing or in irrelevant keywords share an entry.
+type FormatterKey = (DateTimeFormatterPreferences, CompositeFieldSet);
+type PatternKey = (DateTimeFormatterPreferences, String);
+
+type PatternCache = RefCell<Lru<PatternKey, Box<dyn Any>>>;
+
+thread_local! {
+    static FORMATTERS: RefCell<Lru<FormatterKey, Formatter>> = const { RefCell::new(Lru::new()) };
+    static PATTERNS: PatternCache = const { RefCell::new(Lru::new()) };
+    // The patterns that `date-symbols` and `month-grid` use. They are kept
+    // apart, so that they don't evict the patterns of `experimental-pattern`
+    // and aren't counted in the statistics.
+    static INTERNAL_PATTERNS: PatternCache = const { RefCell::new(Lru::new()) };
+}
+
+/// Calls `f` with a (cached) formatter for `prefs` and `fields`.
+pub fn with_formatter<R>(
+    prefs: DateTimeFormatterPreferences,
+    fields: CompositeFieldSet,
+    f: impl FnOnce(&Formatter) -> Result<R, crate::Error>,
+) -> Result<R, crate::Error> {
+    let key = (prefs.clone(), fields);
+    FORMATTERS.with_borrow_mut(|cache| {
+        let formatter = cache.get_or_try_insert(key, || {
+            crate::data::create(
+                |provider| Inner::try_new_with_buffer_provider(provider, prefs.clone(), fields),
+                || Inner::try_new(prefs, fields),
+            )
+            .map(Formatter)
+            .map_err(crate::Error::LoadDateTimeFormatter)
+        })?;
+        f(formatter)
+    })
+}
+
+#[cfg(feature = "non-gregorian")]
+type Inner = DateTimeFormatter<CompositeFieldSet>;
+#[cfg(not(feature = "non-gregorian"))]
+type Inner = FixedCalendarDateTimeFormatter<Gregorian, CompositeFieldSet>;
+
+/// A date/time formatter for values in the ISO calendar.
+///
+/// Without the `non-gregorian` feature, this is a formatter for the Gregorian
+/// calendar only, so that the data of the other calendars isn't included.
+pub struct Formatter(Inner);
+
+impl Formatter {
+    pub fn format(
+        &self,
+        value: &ZonedDateTime<Iso, TimeZoneInfo<AtTime>>,
+    ) -> FormattedDateTime<'_> {
+        #[cfg(feature = "non-gregorian")]
+        return self.0.format(value);
+        #[cfg(not(feature = "non-gregorian"))]
+        return self.0.format(&ZonedDateTime {
+            date: value.date.to_calendar(Gregorian),
+            time: value.time,
+            zone: value.zone,
+        });
+    }
+}
+
+/// Calls `f` with a (cached) value for the pattern `pattern` with `prefs`
+/// (which must have the resolved calendar).
+///
+/// The value is created by `create` and holds the names (and the parsed
+/// pattern) for a specific calendar, which is why it's type-erased.
+pub fn with_pattern<T: 'static, R>(
+    prefs: &DateTimeFormatterPreferences,
+    pattern: &str,
+    create: impl FnOnce() -> Result<T, crate::Error>,
+    f: impl FnOnce(&T) -> Result<R, crate::Error>,
+) -> Result<R, crate::Error> {
+    with_pattern_in(&PATTERNS, prefs, pattern, create, f)
+}
+
+/// Like [`with_pattern`], but for patterns that the plugin uses internally.
+pub fn with_internal_pattern<T: 'static, R>(
+    prefs: &DateTimeFormatterPreferences,
+    pattern: &str,
+    create: impl FnOnce() -> Result<T, crate::Error>,
+    f: impl FnOnce(&T) -> Result<R, crate::Error>,
+) -> Result<R, crate::Error> {
+    with_pattern_in(&INTERNAL_PATTERNS, prefs, pattern, create, f)
+}
+
+fn with_pattern_in<T: 'static, R>(
+    cache: &'static LocalKey<PatternCache>,
+    prefs: &DateTimeFormatterPreferences,
+    pattern: &str,
+    create: impl FnOnce() -> Result<T, crate::Error>,
+    f: impl FnOnce(&T) -> Result<R, crate::Error>,
+) -> Result<R, crate::Error> {
+    let key = (prefs.clone(), pattern.to_owned());
+    cache.with_borrow_mut(|cache| {
+        let value = cache.get_or_try_insert(key, || Ok(Box::new(create()?) as Box<dyn Any>))?;
+        f(value
+            .downcast_ref::<T>()
+            .expect("the calendar (and thus the type) is part of the key"))
+    })
+}
+
+/// The hits and misses of the formatter cache during one plugin call.
+#[derive(Serialize)]
+pub struct CallStats {
+    hits: u64,
+    misses: u64,
+}
+
+/// Calls `f` and counts the hits and misses of the formatter cache in it.
+///
+/// Unlike [`stats`], this doesn't depend on previous calls reaching the same
+/// instance of the plugin.
+pub fn count_formatters<R>(f: impl FnOnce() -> R) -> (R, CallStats) {
+    let counts = || FORMATTERS.with_borrow(|it| (it.hits, it.misses));
+    let (hits, misses) = counts();
+    let res = f();
+    let (hits_after, misses_after) = counts();
+
+    let stats = CallStats {
+        hits: hits_after - hits,
+        misses: misses_after - misses,
+    };
+    (res, stats)
+}
+
+/// Removes all entries from the caches (the statistics are kept).
+pub fn clear() {
+    FORMATTERS.with_borrow_mut(Lru::clear);
+    PATTERNS.with_borrow_mut(Lru::clear);
+    INTERNAL_PATTERNS.with_borrow_mut(Lru::clear);
+}
+
+#[derive(Serialize)]
+struct Stats {
+    hits: u64,
+    misses: u64,
+    entries: usize,
+    capacity: usize,
+}
+
+#[derive(Serialize)]
+struct AllStats {
+    formatters: Stats,
+    patterns: Stats,
+}
+
+/// Serializes the hit and miss counts of the caches.
+///
+/// These are the counts of the current instance of the plugin. Typst doesn't
+/// guarantee that all calls reach the same instance, so this is only meant
+/// for diagnostics.
+pub fn stats() -> Result<Vec<u8>, crate::Error> {
+    let stats = AllStats {
+        formatters: FORMATTERS.with_borrow(Lru::stats),
+        patterns: PATTERNS.with_borrow(Lru::stats),
+    };
+
+    let mut w = vec![];
+    ciborium::into_writer(&stats, &mut w)?;
+    Ok(w)
+}
+
+/// A least recently used cache. The most recently used entry is at the end.
+///
+/// This uses a linear search, because the keys aren't hashable and the
+/// capacity is small.
+struct Lru<K, V> {
+    entries: Vec<(K, V)>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K: PartialEq, V> Lru<K, V> {
+    const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn get_or_try_insert(
+        &mut self,
+        key: K,
+        create: impl FnOnce() -> Result<V, crate::Error>,
+    ) -> Result<&V, crate::Error> {
+        if let Some(idx) = self.entries.iter().position(|(k, _)| *k == key) {
+            self.hits += 1;
+            let entry = self.entries.remove(idx);
+            self.entries.push(entry);
+        } else {
+            self.misses += 1;
+            let value = create()?;
+            if self.entries.len() >= CAPACITY {
+                self.entries.remove(0);
+            }
+            self.entries.push((key, value));
+        }
+
+        Ok(&self.entries[self.entries.len() - 1].1)
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.entries.len(),
+            capacity: CAPACITY,
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! Selection of the calendar that dates are formatted in and conversion
+//! between calendars.
+
+use icu_calendar::{
+    preferences::{CalendarAlgorithm, CalendarPreferences, HijriCalendarAlgorithm},
+    AsCalendar, Date, Iso,
+};
+use icu_datetime::DateTimeFormatterPreferences;
+use serde::Serialize;
+
+use crate::{
+    format::{SpecInput, SpecifiedZonedDateTime},
+    serde::CalendarSerde,
+};
+
+/// Resolves the calendar that `prefs` select (explicitly or through the
+/// region) and checks that dates can be formatted in it.
+pub fn resolve(prefs: &DateTimeFormatterPreferences) -> Result<CalendarAlgorithm, crate::Error> {
+    // the default calendar of some regions (e.g. Thailand) isn't included
+    #[cfg(not(feature = "non-gregorian"))]
+    if prefs.calendar_algorithm.is_none() {
+        return Ok(CalendarAlgorithm::Gregory);
+    }
+
+    let algorithm = CalendarPreferences::from(prefs).resolved_algorithm();
+    if is_supported(algorithm) && is_included(algorithm) {
+        Ok(algorithm)
+    } else {
+        Err(unavailable(algorithm))
+    }
+}
+
+/// The error for a calendar that dates can't be formatted in.
+pub fn unavailable(algorithm: CalendarAlgorithm) -> crate::Error {
+    if is_supported(algorithm) {
+        crate::Error::ExcludedCalendar(name(algorithm))
+    } else {
+        crate::Error::UnsupportedCalendar(name(algorithm))
+    }
+}
+
+/// Checks if the calendar is included in this build (see the `non-gregorian`
+/// feature).
+fn is_included(algorithm: CalendarAlgorithm) -> bool {
+    cfg!(feature = "non-gregorian") || algorithm == CalendarAlgorithm::Gregory
+}
+
+/// Checks if dates can be formatted in the calendar in builds with all
+/// calendars (see [`for_each_calendar`]).
+fn is_supported(algorithm: CalendarAlgorithm) -> bool {
+    crate::calendar::for_each_calendar!(supported algorithm)
+}
+
+/// The BCP-47 identifier of the calendar (the value of `-u-ca`).
+pub fn name(algorithm: CalendarAlgorithm) -> String {
+    match algorithm {
+        CalendarAlgorithm::Buddhist => "buddhist".into(),
+        CalendarAlgorithm::Chinese => "chinese".into(),
+        CalendarAlgorithm::Coptic => "coptic".into(),
+        CalendarAlgorithm::Dangi => "dangi".into(),
+        CalendarAlgorithm::Ethioaa => "ethioaa".into(),
+        CalendarAlgorithm::Ethiopic => "ethiopic".into(),
+        CalendarAlgorithm::Gregory => "gregory".into(),
+        CalendarAlgorithm::Hebrew => "hebrew".into(),
+        CalendarAlgorithm::Indian => "indian".into(),
+        CalendarAlgorithm::Hijri(None) => "islamic".into(),
+        CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Umalqura)) => {
+            "islamic-umalqura".into()
+        }
+        CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Tbla)) => "islamic-tbla".into(),
+        CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Civil)) => "islamic-civil".into(),
+        CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Rgsa)) => "islamic-rgsa".into(),
+        CalendarAlgorithm::Iso8601 => "iso8601".into(),
+        CalendarAlgorithm::Japanese => "japanese".into(),
+        CalendarAlgorithm::Persian => "persian".into(),
+        CalendarAlgorithm::Roc => "roc".into(),
+        // both enums are non-exhaustive, the keyword value is the identifier
+        other => icu_locale_core::extensions::unicode::Value::from(other).to_string(),
+    }
+}
+
+/// The calendars that dates can be formatted in, as `pattern => calendar`.
+///
+/// This is the only list of supported calendars. `dispatch` expands to the
+/// `match` of [`with_calendar`] (calendars other than the Gregorian one are
+/// only matched with the `non-gregorian` feature) and `supported` checks if a
+/// [`CalendarAlgorithm`] is in the list (regardless of the features).
+///
+/// See <https://github.com/unicode-org/icu4x/blob/icu%402.2.0/components/datetime/src/scaffold/calendar.rs#L449-L488>
+macro_rules! for_each_calendar {
+    ($mode:ident $($args:tt)*) => {
+        crate::calendar::for_each_calendar!(@$mode [$($args)*]
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Buddhist => Buddhist,
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Chinese => ChineseTraditional::new(),
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Coptic => Coptic,
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Dangi => KoreanTraditional::new(),
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Ethiopic => Ethiopian::new(),
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Ethioaa => {
+                Ethiopian::new_with_era_style(EthiopianEraStyle::AmeteAlem)
+            },
+            CalendarAlgorithm::Gregory => Gregorian,
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Hebrew => Hebrew,
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Indian => Indian,
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Civil)) => {
+                Hijri::<hijri::TabularAlgorithm>::new_tabular(
+                    HijriTabularLeapYears::TypeII,
+                    HijriTabularEpoch::Friday,
+                )
+            },
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Tbla)) => {
+                Hijri::<hijri::TabularAlgorithm>::new_tabular(
+                    HijriTabularLeapYears::TypeII,
+                    HijriTabularEpoch::Thursday,
+                )
+            },
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Umalqura)) => {
+                Hijri::<hijri::UmmAlQura>::new_umm_al_qura()
+            },
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Japanese => Japanese::new(),
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Persian => Persian,
+            #[cfg(feature = "non-gregorian")]
+            CalendarAlgorithm::Roc => Roc,
+        )
+    };
+    (@dispatch [$algorithm:expr, |$cal:ident| $body:expr]
+        $($(#[$meta:meta])* $pattern:pat => $calendar:expr,)*
+    ) => {
+        match $algorithm {
+            $(
+                $(#[$meta])*
+                $pattern => {
+                    let $cal = $calendar;
+                    $body
+                }
+            )*
+            other => Err(crate::calendar::unavailable(other)),
+        }
+    };
+    (@supported [$algorithm:expr]
+        $($(#[$meta:meta])* $pattern:pat => $calendar:expr,)*
+    ) => {
+        matches!($algorithm, $($pattern)|*)
+    };
+}
+
+pub(crate) use for_each_calendar;
+
+/// Evaluates `$body` with `$cal` bound to the ICU4X calendar for
+/// `$algorithm`.
+///
+/// `$body` is expanded once per calendar (see [`for_each_calendar`]), so it
+/// can be generic over the calendar type. It must evaluate to a
+/// `Result<_, crate::Error>`. Unsupported calendars (see [`resolve`]) result
+/// in an error.
+macro_rules! with_calendar {
+    ($algorithm:expr, |$cal:ident| $body:expr) => {{
+        use icu_calendar::{cal::Gregorian, preferences::CalendarAlgorithm};
+        #[cfg(feature = "non-gregorian")]
+        use icu_calendar::{
+            cal::{
+                hijri, Buddhist, ChineseTraditional, Coptic, Ethiopian, EthiopianEraStyle, Hebrew,
+                Hijri, HijriTabularEpoch, HijriTabularLeapYears, Indian, Japanese,
+                KoreanTraditional, Persian, Roc,
+            },
+            preferences::HijriCalendarAlgorithm,
+        };
+
+        crate::calendar::for_each_calendar!(dispatch $algorithm, |$cal| $body)
+    }};
+}
+
+pub(crate) use with_calendar;
+
+/// The fields of a date in some calendar.
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct CalendarDate {
+    era: Option<String>,
+    era_year: Option<i32>,
+    /// The year in the 60 year cycle (Chinese and Dangi calendars).
+    cyclic_year: Option<u8>,
+    related_iso: Option<i32>,
+    extended_year: i32,
+    month_code: String,
+    ordinal_month: u8,
+    is_leap_month: bool,
+    day: u8,
+    /// ISO weekday (1 = Monday, 7 = Sunday).
+    day_of_week: u8,
+    day_of_year: u16,
+    days_in_month: u8,
+    days_in_year: u16,
+    months_in_year: u8,
+    is_leap_year: bool,
+}
+
+/// Converts the date of `spec` to `calendar` and serializes its fields.
+pub fn convert(spec: SpecInput, calendar: CalendarSerde) -> Result<Vec<u8>, crate::Error> {
+    let spec: SpecifiedZonedDateTime = spec.try_into()?;
+    if !spec.has_date {
+        return Err(crate::Error::MissingValues);
+    }
+
+    let date = spec.value.date;
+    let converted = match CalendarAlgorithm::from(calendar) {
+        // ISO dates can't be formatted, so they're not part of `with_calendar`
+        CalendarAlgorithm::Iso8601 => Ok(fields_in(date, Iso)),
+        algorithm => with_calendar!(algorithm, |cal| Ok(fields_in(date, cal))),
+    }?;
+
+    let mut w = vec![];
+    ciborium::into_writer(&converted, &mut w)?;
+    Ok(w)
+}
+
+fn fields_in<C: AsCalendar>(date: Date<Iso>, cal: C) -> CalendarDate {
+    let date = date.to_calendar(cal);
+    let year = date.year();
+    let era = year.era();
+    let cyclic = year.cyclic();
+    let month = date.month();
+
+    CalendarDate {
+        era: era.as_ref().map(|it| it.era.to_string()),
+        era_year: era.as_ref().map(|it| it.year),
+        cyclic_year: cyclic.as_ref().map(|it| it.year),
+        related_iso: cyclic.as_ref().map(|it| it.related_iso),
+        extended_year: date.extended_year(),
+        month_code: month.standard_code.0.to_string(),
+        ordinal_month: month.ordinal,
+        is_leap_month: month.is_leap(),
+        day: date.day_of_month().0,
+        day_of_week: date.day_of_week() as u8,
+        day_of_year: date.day_of_year().0,
+        days_in_month: date.days_in_month(),
+        days_in_year: date.days_in_year(),
+        months_in_year: date.months_in_year(),
+        is_leap_year: date.is_in_leap_year(),
+    }
+}
@@ -0,0 +1,43 @@
+use icu_collator::{
+    options::CollatorOptions, preferences::CollationNumericOrdering, Collator, CollatorPreferences,
+};
+
+use crate::serde::CollatorOptionsSerde;
+
+pub fn sort(
+    items: Vec<String>,
+    locale: &str,
+    opts: CollatorOptionsSerde,
+) -> Result<Vec<u8>, crate::Error> {
+    let mut prefs = CollatorPreferences::from(crate::data::locale(locale)?);
+    if let Some(case_first) = opts.case_first {
+        prefs.case_first = Some(case_first.into());
+    }
+    if let Some(numeric) = opts.numeric {
+        prefs.numeric_ordering = Some(if numeric {
+            CollationNumericOrdering::True
+        } else {
+            CollationNumericOrdering::False
+        });
+    }
+
+    let mut options = CollatorOptions::default();
+    options.strength = opts.strength.map(Into::into);
+    options.alternate_handling = opts.alternate_handling.map(Into::into);
+
+    let collator = Collator::try_new(prefs, options)?;
+
+    // sort_by is stable, so equal items keep their relative order
+    let mut indices: Vec<usize> = (0..items.len()).collect();
+    indices.sort_by(|&a, &b| collator.compare(&items[a], &items[b]));
+
+    let mut w = vec![];
+    if opts.indices.unwrap_or_default() {
+        ciborium::into_writer(&indices, &mut w)?;
+    } else {
+        let sorted: Vec<_> = indices.into_iter().map(|i| &items[i]).collect();
+        ciborium::into_writer(&sorted, &mut w)?;
+    }
+
+    Ok(w)
+}
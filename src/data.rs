@@ -0,0 +1,196 @@
+//! Locale data that's loaded at runtime.
+//!
+//! By default, all data is compiled into the plugin. A postcard blob (exported
+//! with `icu4x-datagen --format blob`) can be loaded to use newer or additional
+//! data. Data that isn't in the blob is taken from the compiled data.
+//!
+//! Only the date/time formatters (and the checks for `strict`) are created
+//! through [`create`]. The other formatters (numbers, lists, display names,
+//! ...) always use the compiled data.
+
+use std::{cell::RefCell, str::FromStr};
+
+use icu_locale::LocaleFallbacker;
+use icu_locale_core::{subtags::Language, Locale};
+use icu_provider::{
+    buf::{AsDeserializingBufferProvider, BufferMarker, DeserializingBufferProvider},
+    DataError, DataErrorKind, DataIdentifierBorrowed, DataLocale, DataMarker, DataMarkerInfo,
+    DataProvider, DataRequest, DataResponse, DynamicDataProvider,
+};
+use icu_provider_adapters::fallback::LocaleFallbackProvider;
+use icu_provider_blob::BlobDataProvider;
+
+/// The provider for the loaded blob.
+///
+/// The blob is searched with locale fallback, but only data for the language
+/// of the requested locale is used. If the blob only has the root locale's data
+/// (i.e. the blob doesn't include the language), the data is reported as
+/// missing, so that [`create`] uses the compiled data for the language instead.
+pub struct Provider(LocaleFallbackProvider<BlobDataProvider>);
+
+impl DynamicDataProvider<BufferMarker> for Provider {
+    fn load_data(
+        &self,
+        marker: DataMarkerInfo,
+        req: DataRequest,
+    ) -> Result<DataResponse<BufferMarker>, DataError> {
+        let res = self.0.load_data(marker, req)?;
+        let requested = req.id.locale;
+        // The fallback provider reports the locale that the data is from.
+        let is_own_language = res.metadata.locale.as_ref().is_none_or(|it| {
+            requested.language == Language::UNKNOWN || it.language == requested.language
+        });
+        if is_own_language {
+            Ok(res)
+        } else {
+            Err(DataErrorKind::IdentifierNotFound.with_req(marker, req))
+        }
+    }
+}
+
+/// [`Provider`] for constructors that need a [`DataProvider`](icu_provider::DataProvider).
+pub type Deserializing<'a> = DeserializingBufferProvider<'a, Provider>;
+
+thread_local! {
+    static PROVIDER: RefCell<Option<Provider>> = const { RefCell::new(None) };
+}
+
+#[cfg(not(any(feature = "all-locales", feature = "locales-eu")))]
+compile_error!("no locales are selected - enable `all-locales` or a subset like `locales-eu`");
+
+// The compiled data has all locales and time zone names. Builds without them
+// need data that's generated for the selected features (see `build.rs`).
+#[cfg(all(not(feature = "all-locales"), not(icu4x_custom_data)))]
+compile_error!(
+    "builds without `all-locales` need data for the selected locales - \
+     build with `just build-slim` or set `ICU4X_DATA_DIR` to the output of \
+     `icu4x-datagen --format baked --locales <locales/*.txt>`"
+);
+#[cfg(all(not(feature = "zone-names"), not(icu4x_custom_data)))]
+compile_error!(
+    "builds without `zone-names` need data without time zone names - \
+     build with `just build-slim` or set `ICU4X_DATA_DIR` to data with the \
+     time zone names (except for the essentials) exported for `und` only"
+);
+
+/// The languages that the compiled data includes (one per line) if it doesn't
+/// include all locales. Must match the locales passed to `icu4x-datagen`.
+#[cfg(all(feature = "locales-eu", not(feature = "all-locales")))]
+const LANGUAGES: &str = include_str!("../locales/eu.txt");
+
+/// Parses `locale` and checks that the compiled data includes it.
+///
+/// ICU4X silently falls back to the root locale if some locale isn't included,
+/// so this gives a clear error in builds with a subset of the locales.
+pub fn locale(locale: &str) -> Result<Locale, crate::Error> {
+    let locale = Locale::from_str(locale)?;
+
+    #[cfg(not(feature = "all-locales"))]
+    {
+        let language = locale.id.language.as_str();
+        let included = language == "und"
+            || LANGUAGES.lines().any(|it| it.trim() == language)
+            || PROVIDER.with_borrow(Option::is_some);
+        if !included {
+            return Err(crate::Error::ExcludedLocale(locale.to_string()));
+        }
+    }
+
+    Ok(locale)
+}
+
+/// Loads the data from `blob`, replacing previously loaded data.
+///
+/// This clears the formatter caches, because they might hold formatters that
+/// were created with different data.
+pub fn load(blob: &[u8]) -> Result<(), crate::Error> {
+    let provider =
+        BlobDataProvider::try_new_from_blob(blob.into()).map_err(crate::Error::LoadData)?;
+    let provider = Provider(LocaleFallbackProvider::new(
+        provider,
+        LocaleFallbacker::new().static_to_owned(),
+    ));
+
+    PROVIDER.set(Some(provider));
+    crate::cache::clear();
+    Ok(())
+}
+
+/// Creates a value with the loaded data through `from_blob`.
+///
+/// If no data is loaded or the blob doesn't have the data (markers) that are
+/// needed, the value is created from the compiled data with `compiled`.
+pub fn create<T, E: MissingData>(
+    from_blob: impl FnOnce(&Provider) -> Result<T, E>,
+    compiled: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E> {
+    PROVIDER.with_borrow(|provider| match provider.as_ref().map(from_blob) {
+        Some(Err(e)) if e.is_missing_data() => compiled(),
+        Some(res) => res,
+        None => compiled(),
+    })
+}
+
+/// Checks if there's data for `M` in `locale` itself (i.e. without falling
+/// back to a parent locale).
+pub fn has_data<M>(locale: &DataLocale) -> Result<bool, crate::Error>
+where
+    M: DataMarker,
+    icu_datetime::provider::Baked: DataProvider<M>,
+    for<'a> Deserializing<'a>: DataProvider<M>,
+{
+    let request = || DataRequest {
+        id: DataIdentifierBorrowed::for_locale(locale),
+        ..Default::default()
+    };
+    // Providers with fallback report the locale that the data is from.
+    let is_exact = |res: Result<DataResponse<M>, DataError>| match res {
+        Ok(res) => Ok(res.metadata.locale.is_none_or(|it| it == *locale)),
+        Err(e) if e.kind == DataErrorKind::IdentifierNotFound => Ok(false),
+        Err(e) => Err(e),
+    };
+
+    create(
+        |provider| is_exact(provider.as_deserializing().load(request())),
+        || is_exact(icu_datetime::provider::Baked.load(request())),
+    )
+    .map_err(Into::into)
+}
+
+/// Errors that can signal that a data provider doesn't have some data.
+pub trait MissingData {
+    fn is_missing_data(&self) -> bool;
+}
+
+impl MissingData for DataError {
+    fn is_missing_data(&self) -> bool {
+        matches!(
+            self.kind,
+            DataErrorKind::MarkerNotFound | DataErrorKind::IdentifierNotFound
+        )
+    }
+}
+
+impl MissingData for icu_datetime::DateTimeFormatterLoadError {
+    fn is_missing_data(&self) -> bool {
+        matches!(self, Self::Data(e) if e.is_missing_data())
+            || matches!(self, Self::Names(e) if e.is_missing_data())
+    }
+}
+
+impl MissingData for icu_datetime::pattern::PatternLoadError {
+    fn is_missing_data(&self) -> bool {
+        matches!(self, Self::Data(e, _) if e.is_missing_data())
+    }
+}
+
+impl MissingData for crate::Error {
+    fn is_missing_data(&self) -> bool {
+        match self {
+            Self::DataError(e) => e.is_missing_data(),
+            Self::LoadDateTimeFormatter(e) => e.is_missing_data(),
+            Self::PatternLoadError(e) => e.is_missing_data(),
+            _ => false,
+        }
+    }
+}
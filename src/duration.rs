@@ -0,0 +1,94 @@
+//! Durations like "2 hr, 5 min" or "1:02:03" (see ECMA-402 `Intl.DurationFormat`).
+
+use icu_experimental::duration::{
+    options::{DurationFormatterOptions, FractionalDigits},
+    Duration, DurationFormatter, DurationSign, ValidatedDurationFormatterOptions,
+};
+use serde::Deserialize;
+
+use crate::serde::DurationOptionsSerde;
+
+/// The fields of a duration. All fields must have the same sign.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DurationSpec {
+    years: i64,
+    months: i64,
+    weeks: i64,
+    days: i64,
+    hours: i64,
+    minutes: i64,
+    seconds: i64,
+    milliseconds: i64,
+    microseconds: i64,
+    nanoseconds: i64,
+}
+
+impl TryFrom<DurationSpec> for Duration {
+    type Error = crate::Error;
+
+    fn try_from(value: DurationSpec) -> Result<Self, Self::Error> {
+        let fields = [
+            value.years,
+            value.months,
+            value.weeks,
+            value.days,
+            value.hours,
+            value.minutes,
+            value.seconds,
+            value.milliseconds,
+            value.microseconds,
+            value.nanoseconds,
+        ];
+        let sign = if fields.iter().all(|it| *it >= 0) {
+            DurationSign::Positive
+        } else if fields.iter().all(|it| *it <= 0) {
+            DurationSign::Negative
+        } else {
+            return Err(crate::Error::MixedDurationSigns);
+        };
+
+        Ok(Self {
+            sign,
+            years: value.years.unsigned_abs(),
+            months: value.months.unsigned_abs(),
+            weeks: value.weeks.unsigned_abs(),
+            days: value.days.unsigned_abs(),
+            hours: value.hours.unsigned_abs(),
+            minutes: value.minutes.unsigned_abs(),
+            seconds: value.seconds.unsigned_abs(),
+            milliseconds: value.milliseconds.unsigned_abs(),
+            microseconds: value.microseconds.unsigned_abs(),
+            nanoseconds: value.nanoseconds.unsigned_abs(),
+        })
+    }
+}
+
+pub fn format(
+    spec: DurationSpec,
+    locale: &str,
+    opts: DurationOptionsSerde,
+) -> Result<Vec<u8>, crate::Error> {
+    let prefs = crate::data::locale(locale)?.into();
+    let duration = Duration::try_from(spec)?;
+
+    let mut options = DurationFormatterOptions::default();
+    if let Some(style) = opts.style {
+        options.base = style.into();
+    }
+    if let Some(digits) = opts.fractional_digits {
+        if digits > 9 {
+            return Err(crate::Error::FractionDigits(
+                "the duration options",
+                "there can be at most 9 digits",
+            ));
+        }
+        options.fractional_digits = FractionalDigits::Fixed(digits);
+    }
+    let options = ValidatedDurationFormatterOptions::validate(options)
+        .map_err(crate::Error::DurationOptions)?;
+
+    let formatter = DurationFormatter::try_new(prefs, options)?;
+
+    Ok(crate::write::to_vec(formatter.format(&duration)))
+}
@@ -1,25 +1,81 @@
-use std::str::FromStr;
-
+use icu_calendar::{
+    options::{DateFromFieldsOptions, Overflow},
+    preferences::{CalendarAlgorithm, CalendarPreferences},
+    types::{DateFields as CalendarDateFields, RataDie},
+    AnyCalendar, AnyCalendarKind, AsCalendar, Date, Iso,
+};
 use icu_datetime::{
     fieldsets::{builder::FieldSetBuilder, enums::CompositeFieldSet},
-    DateTimeFormatter,
+    DateTimeFormatterPreferences,
 };
 use icu_locale_core::Locale;
 use icu_time::{
     zone::iana::IanaParserExtendedBorrowed, DateTime, TimeZone, TimeZoneInfo, ZonedDateTime,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use writeable::Writeable;
+
+use crate::{
+    serde::{CalendarSerde, DisambiguationSerde},
+    write::{Output, PartsCollector, Segment},
+};
+
+/// A [`Spec`] or an IXDTF string (e.g. `2025-03-14T09:30:00+01:00[Europe/Berlin]`).
+pub enum SpecInput {
+    Ixdtf(String),
+    Fields(Spec),
+}
+
+impl<'de> Deserialize<'de> for SpecInput {
+    /// Dispatches on the CBOR type (instead of `#[serde(untagged)]`) to keep
+    /// the errors of the fields of a [`Spec`].
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = SpecInput;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an IXDTF string or a map of date and time fields")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<SpecInput, E> {
+                Ok(SpecInput::Ixdtf(v.to_owned()))
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                map: A,
+            ) -> Result<SpecInput, A::Error> {
+                Spec::deserialize(serde::de::value::MapAccessDeserializer::new(map))
+                    .map(SpecInput::Fields)
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
 
 #[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct Spec {
+    /// The calendar of the date fields (defaults to ISO).
+    pub calendar: Option<CalendarSerde>,
+    /// If specified, `year` is the year in this era.
+    pub era: Option<String>,
     pub year: Option<i32>,
     pub month: Option<u8>,
+    /// Alternative to `month` which can specify leap months (e.g. `M05L`).
+    pub month_code: Option<String>,
     pub day: Option<u8>,
     pub hour: Option<u8>,
     pub minute: Option<u8>,
     pub second: Option<u8>,
     pub nanosecond: Option<u32>,
     pub zone: Option<TimezoneSpec>,
+    /// The `u-ca` annotation of an IXDTF string.
+    #[serde(skip)]
+    pub calendar_annotation: Option<CalendarAlgorithm>,
 }
 
 #[derive(Deserialize)]
@@ -28,6 +84,21 @@ pub struct TimezoneSpec {
     offset: Option<UtcOffsetSpec>,
     iana: Option<String>,
     bcp47: Option<String>,
+    disambiguation: Option<DisambiguationSerde>,
+    /// Check that `offset` is used by the zone (defaults to `true`).
+    validate_offset: Option<bool>,
+}
+
+impl TimezoneSpec {
+    pub fn new(offset: Option<UtcOffsetSpec>, iana: Option<String>) -> Self {
+        Self {
+            offset,
+            iana,
+            bcp47: None,
+            disambiguation: None,
+            validate_offset: None,
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -37,8 +108,14 @@ pub enum UtcOffsetSpec {
     Chars(String),
 }
 
-pub fn format(spec: Spec, locale: &str, builder: FieldSetBuilder) -> Result<Vec<u8>, crate::Error> {
-    let locale = Locale::from_str(locale)?;
+pub fn format(
+    spec: SpecInput,
+    locale: &str,
+    builder: FieldSetBuilder,
+    calendar: Option<CalendarAlgorithm>,
+    output: Output,
+) -> Result<Vec<u8>, crate::Error> {
+    let locale = crate::data::locale(locale)?;
     let fields = builder.build_composite()?;
 
     let spec: SpecifiedZonedDateTime = spec.try_into()?;
@@ -46,21 +123,267 @@ pub fn format(spec: Spec, locale: &str, builder: FieldSetBuilder) -> Result<Vec<
         return Err(crate::Error::MissingValues);
     }
 
-    let formatter = DateTimeFormatter::try_new(locale.into(), fields)
-        .map_err(crate::Error::LoadDateTimeFormatter)?;
+    let prefs = spec.preferences(&locale, calendar)?;
+    crate::cache::with_formatter(prefs, fields, |formatter| {
+        output.write(formatter.format(&spec.value))
+    })
+}
+
+/// The result of a single item in [`format_batch`].
+#[derive(Serialize)]
+#[serde(untagged)]
+enum BatchItem {
+    Formatted(String),
+    Error { error: String },
+}
+
+/// The result of [`format_batch`] with the cache statistics of the call.
+#[derive(Serialize)]
+struct BatchWithStats {
+    items: Vec<BatchItem>,
+    stats: crate::cache::CallStats,
+}
+
+/// Formats all `specs` with the same options.
+///
+/// Errors of individual items are reported as `{error: "..."}` in place of
+/// the formatted string. With `stats`, the items are returned together with
+/// the hits and misses of the formatter cache in this call.
+pub fn format_batch(
+    specs: Vec<ciborium::Value>,
+    locale: &str,
+    builder: FieldSetBuilder,
+    calendar: Option<CalendarAlgorithm>,
+    stats: bool,
+) -> Result<Vec<u8>, crate::Error> {
+    let locale = crate::data::locale(locale)?;
+    let fields = builder.build_composite()?;
+
+    let (items, call_stats) = crate::cache::count_formatters(|| {
+        specs
+            .into_iter()
+            .map(|spec| match format_item(spec, &locale, fields, calendar) {
+                Ok(formatted) => BatchItem::Formatted(formatted),
+                Err(e) => BatchItem::Error {
+                    error: e.to_string(),
+                },
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let mut w = vec![];
+    if stats {
+        let items = BatchWithStats {
+            items,
+            stats: call_stats,
+        };
+        ciborium::into_writer(&items, &mut w)?;
+    } else {
+        ciborium::into_writer(&items, &mut w)?;
+    }
+    Ok(w)
+}
+
+fn format_item(
+    spec: ciborium::Value,
+    locale: &Locale,
+    fields: CompositeFieldSet,
+    calendar: Option<CalendarAlgorithm>,
+) -> Result<String, crate::Error> {
+    let spec: SpecifiedZonedDateTime = spec
+        .deserialized::<SpecInput>()
+        .map_err(|ciborium::value::Error::Custom(it)| crate::Error::DeValue("item", it))?
+        .try_into()?;
+    if !spec.check_fieldset(&fields) {
+        return Err(crate::Error::MissingValues);
+    }
+
+    let prefs = spec.preferences(locale, calendar)?;
+    crate::cache::with_formatter(prefs, fields, |formatter| {
+        Ok(formatter.format(&spec.value).write_to_string().into_owned())
+    })
+}
+
+/// Formats the range between `from` and `to`.
+///
+/// ICU4X doesn't provide interval formats yet, so the fields that both ends
+/// have in common are collapsed manually: both ends are formatted with the
+/// same fields and only the fields from the greatest difference down (e.g. the
+/// day in "Jan 3 – 7, 2025") are repeated. The text around them is shared. The
+/// ends are joined with the interval fallback of the locale (e.g. `{0} – {1}`).
+pub fn format_range(
+    from: SpecInput,
+    to: SpecInput,
+    locale: &str,
+    builder: FieldSetBuilder,
+    calendar: Option<CalendarAlgorithm>,
+) -> Result<Vec<u8>, crate::Error> {
+    let locale = crate::data::locale(locale)?;
+    let fields = builder.build_composite()?;
+
+    let from: SpecifiedZonedDateTime = from.try_into()?;
+    let to: SpecifiedZonedDateTime = to.try_into()?;
+    if !from.check_fieldset(&fields) || !to.check_fieldset(&fields) {
+        return Err(crate::Error::MissingValues);
+    }
+    if !from.has_same_zone(&to) {
+        return Err(crate::Error::RangeZoneMismatch);
+    }
+    let prefs = from.preferences(&locale, calendar)?;
+    if prefs.calendar_algorithm != to.preferences(&locale, calendar)?.calendar_algorithm {
+        return Err(crate::Error::RangeCalendarMismatch);
+    }
+    if from.local_key() > to.local_key() {
+        return Err(crate::Error::RangeOrder);
+    }
+
+    let (start, end) = crate::cache::with_formatter(prefs, fields, |formatter| {
+        Ok((
+            PartsCollector::collect(formatter.format(&from.value))?,
+            PartsCollector::collect(formatter.format(&to.value))?,
+        ))
+    })?;
+    let text =
+        |segments: &[Segment]| -> String { segments.iter().map(|it| it.text.as_str()).collect() };
+    if text(&start) == text(&end) {
+        return Ok(text(&end).into_bytes());
+    }
+
+    let join = |a: &[Segment], b: &[Segment]| {
+        interval_fallback(&locale)
+            .replace("{0}", &text(a))
+            .replace("{1}", &text(b))
+    };
+
+    let same_layout =
+        start.len() == end.len() && start.iter().zip(&end).all(|(a, b)| a.part == b.part);
+    if !same_layout {
+        return Ok(join(&start, &end).into_bytes());
+    }
+
+    let (lo, hi) = differing_span(&start, &end);
+    let mut out = text(&end[..lo]);
+    out.push_str(&join(&start[lo..=hi], &end[lo..=hi]));
+    out.push_str(&text(&end[hi + 1..]));
+    Ok(out.into_bytes())
+}
+
+/// The size of the field of a part from the largest (year) to the smallest
+/// (time). Literals and time zones don't have a size.
+fn field_rank(part: &str) -> Option<u8> {
+    match part {
+        "era" | "year" | "relatedYear" | "yearName" => Some(0),
+        "month" => Some(1),
+        "day" | "weekday" => Some(2),
+        "dayPeriod" => Some(3),
+        "hour" | "minute" | "second" => Some(4),
+        _ => None,
+    }
+}
+
+/// Finds the segments that have to be repeated on both ends of a range (as an
+/// inclusive range of indices): all fields that are at most as large as the
+/// largest field that differs. `start` and `end` must have the same parts.
+fn differing_span(start: &[Segment], end: &[Segment]) -> (usize, usize) {
+    let whole = (0, start.len() - 1);
+    let ranks: Vec<_> = start.iter().map(|it| field_rank(it.part)).collect();
+
+    let mut greatest = None;
+    for ((a, b), rank) in start.iter().zip(end).zip(&ranks) {
+        if a.text == b.text {
+            continue;
+        }
+        match rank {
+            // e.g. the zone name changes with daylight saving time
+            None => return whole,
+            Some(rank) => greatest = Some(greatest.map_or(*rank, |it: u8| it.min(*rank))),
+        }
+    }
+    let Some(mut greatest) = greatest else {
+        return whole;
+    };
 
-    Ok(crate::write::to_vec(formatter.format(&spec.value)))
+    loop {
+        let repeated = |i: &usize| ranks[*i].is_some_and(|rank| rank >= greatest);
+        let (Some(lo), Some(hi)) = (
+            (0..start.len()).find(repeated),
+            (0..start.len()).rfind(repeated),
+        ) else {
+            return whole;
+        };
+        // larger fields between the repeated ones (e.g. the month between the
+        // weekday and the day) have to be repeated as well
+        match (lo..=hi).filter_map(|i| ranks[i]).min() {
+            Some(inner) if inner < greatest => greatest = inner,
+            _ => return (lo, hi),
+        }
+    }
+}
+
+/// The pattern that joins the ends of a range (`intervalFormatFallback`).
+///
+/// ICU4X doesn't have data for intervals yet, so this is a copy of the
+/// locales where the fallback differs from the root (`{0} – {1}`). All other
+/// locales get the root's fallback, even if their interval formats use other
+/// punctuation (see the note in `docs/docs/fmt-range.md`). This should be
+/// replaced with the locale's data once ICU4X has interval formats.
+///
+/// See <https://github.com/unicode-org/cldr/tree/main/common/main>
+fn interval_fallback(locale: &Locale) -> &'static str {
+    let id = &locale.id;
+    let is_traditional = id.script.is_some_and(|it| it.as_str() == "Hant")
+        || id
+            .region
+            .is_some_and(|it| matches!(it.as_str(), "TW" | "HK" | "MO"));
+    match id.language.as_str() {
+        "ja" => "{0}～{1}",
+        "ko" => "{0} ~ {1}",
+        "fa" => "{0} تا {1}",
+        "fi" | "sv" => "{0}–{1}",
+        "yue" => "{0} 至 {1}",
+        "zh" if is_traditional => "{0} 至 {1}",
+        _ => "{0} – {1}",
+    }
 }
 
 pub struct SpecifiedZonedDateTime {
     pub value:
         icu_time::ZonedDateTime<icu_calendar::Iso, TimeZoneInfo<icu_time::zone::models::AtTime>>,
-    has_date: bool,
-    has_time: bool,
-    has_zone: bool,
+    pub has_date: bool,
+    pub has_time: bool,
+    pub has_zone: bool,
+    pub calendar_annotation: Option<CalendarAlgorithm>,
 }
 
 impl SpecifiedZonedDateTime {
+    /// The start of the day `date` (in UTC) without a time or zone.
+    pub fn from_date(date: Date<Iso>) -> Self {
+        let time = icu_time::Time::start_of_day();
+        Self {
+            value: ZonedDateTime {
+                date,
+                time,
+                zone: TimeZoneInfo::utc().at_date_time(DateTime { date, time }),
+            },
+            has_date: true,
+            has_time: false,
+            has_zone: false,
+            calendar_annotation: None,
+        }
+    }
+
+    /// The local date and time for ordering values in the same zone.
+    fn local_key(&self) -> (RataDie, u8, u8, u8, u32) {
+        let time = self.value.time;
+        (
+            self.value.date.to_rata_die(),
+            time.hour.number(),
+            time.minute.number(),
+            time.second.number(),
+            time.subsecond.number(),
+        )
+    }
+
     pub fn check_fieldset(&self, set: &CompositeFieldSet) -> bool {
         match set {
             CompositeFieldSet::Date(_) => self.has_date,
@@ -77,25 +400,85 @@ impl SpecifiedZonedDateTime {
             }
         }
     }
+
+    /// Gets the preferences for `locale`. The calendar is selected from (in
+    /// order of precedence) `calendar`, the locale, and the calendar
+    /// annotation. Calendars that dates can't be formatted in are rejected.
+    pub fn preferences(
+        &self,
+        locale: &Locale,
+        calendar: Option<CalendarAlgorithm>,
+    ) -> Result<DateTimeFormatterPreferences, crate::Error> {
+        let mut prefs: DateTimeFormatterPreferences = locale.into();
+        if calendar.is_some() {
+            prefs.calendar_algorithm = calendar;
+        } else if prefs.calendar_algorithm.is_none() {
+            prefs.calendar_algorithm = self.calendar_annotation;
+        }
+        prefs.calendar_algorithm = Some(crate::calendar::resolve(&prefs)?);
+        Ok(prefs)
+    }
+
+    /// Checks if both values are in the same time zone (or both don't have a
+    /// zone). Zones without an ID are compared by their offset.
+    pub fn has_same_zone(&self, other: &Self) -> bool {
+        if self.has_zone != other.has_zone {
+            return false;
+        }
+        if !self.has_zone {
+            return true;
+        }
+
+        let (a, b) = (self.value.zone, other.value.zone);
+        if a.id().is_unknown() || b.id().is_unknown() {
+            a.id() == b.id() && a.offset() == b.offset()
+        } else {
+            a.id() == b.id()
+        }
+    }
+}
+
+impl TryFrom<SpecInput> for Spec {
+    type Error = crate::Error;
+
+    fn try_from(value: SpecInput) -> Result<Self, Self::Error> {
+        match value {
+            SpecInput::Ixdtf(s) => crate::parse::spec_from_ixdtf(&s),
+            SpecInput::Fields(spec) => Ok(spec),
+        }
+    }
+}
+
+impl TryFrom<SpecInput> for SpecifiedZonedDateTime {
+    type Error = crate::Error;
+
+    fn try_from(value: SpecInput) -> Result<Self, Self::Error> {
+        Spec::try_from(value)?.try_into()
+    }
 }
 
 impl TryFrom<Spec> for SpecifiedZonedDateTime {
     type Error = crate::Error;
 
     fn try_from(value: Spec) -> Result<Self, Self::Error> {
-        let (has_date, date) = match (value.year, value.month, value.day) {
-            (Some(y), Some(m), Some(d)) => (
-                true,
-                icu_calendar::Date::try_new_iso(y, m, d).map_err(Self::Error::DateRange)?,
-            ),
-            (None, None, None) => (
+        let has_month = value.month.is_some() || value.month_code.is_some();
+        let (has_date, mut date) = match (value.year, has_month, value.day) {
+            (Some(y), true, Some(d)) => match (&value.calendar, &value.era, &value.month_code) {
+                (None, None, None) => (
+                    true,
+                    Date::try_new_iso(y, value.month.unwrap_or_default(), d)
+                        .map_err(Self::Error::DateRange)?,
+                ),
+                _ => (true, date_from_fields(&value, y, d)?),
+            },
+            (None, false, None) if value.era.is_none() => (
                 false,
-                icu_calendar::Date::try_new_iso(1970, 1, 1).map_err(Self::Error::DateRange)?,
+                Date::try_new_iso(1970, 1, 1).map_err(Self::Error::DateRange)?,
             ),
             _ => return Err(Self::Error::PartialDate),
         };
 
-        let (has_time, time) = if let Some(hour) = value.hour {
+        let (has_time, mut time) = if let Some(hour) = value.hour {
             (
                 true,
                 icu_time::Time::new(
@@ -125,21 +508,50 @@ impl TryFrom<Spec> for SpecifiedZonedDateTime {
         };
 
         let (has_zone, zone) = if let Some(spec) = value.zone {
-            let bcp47 = match (spec.bcp47, spec.iana) {
-                (None, None) => TimeZone::UNKNOWN,
-                (Some(id), None) => TimeZone(id.parse().map_err(Self::Error::IcuLocaleParse)?),
+            let (bcp47, iana) = match (spec.bcp47, spec.iana) {
+                (None, None) => (TimeZone::UNKNOWN, None),
+                (Some(id), None) => (
+                    TimeZone(id.parse().map_err(Self::Error::IcuLocaleParse)?),
+                    None,
+                ),
                 (None, Some(spec)) => {
                     let parsed = IanaParserExtendedBorrowed::new().parse(&spec);
                     if parsed.time_zone.is_unknown() {
                         return Err(Self::Error::UnknownIana);
                     }
-                    parsed.time_zone
+                    (parsed.time_zone, Some(spec))
                 }
                 (Some(_), Some(_)) => return Err(Self::Error::IanaAndBcp47),
             };
-            // XXX: we need to keep track of the date before we use this time zone
+            let offset = match spec.offset {
+                Some(offset) => {
+                    let offset = offset.try_into()?;
+                    if has_date && !bcp47.is_unknown() && spec.validate_offset.unwrap_or(true) {
+                        crate::tzdb::check_offset(
+                            bcp47,
+                            iana.as_deref(),
+                            DateTime { date, time },
+                            offset,
+                        )?;
+                    }
+                    Some(offset)
+                }
+                // the offset can only be resolved at a specific date
+                None if has_date && !bcp47.is_unknown() => {
+                    let (local, offset) = crate::tzdb::resolve_offset(
+                        bcp47,
+                        iana.as_deref(),
+                        DateTime { date, time },
+                        spec.disambiguation.unwrap_or_default(),
+                    )?;
+                    // times in a gap are moved forward or backward
+                    DateTime { date, time } = local;
+                    Some(offset)
+                }
+                None => None,
+            };
             let tz = bcp47
-                .with_offset(spec.offset.map(TryInto::try_into).transpose()?)
+                .with_offset(offset)
                 .at_date_time(DateTime { date, time });
 
             (true, tz)
@@ -155,18 +567,51 @@ impl TryFrom<Spec> for SpecifiedZonedDateTime {
             has_date,
             has_time,
             has_zone,
+            calendar_annotation: value.calendar_annotation,
         })
     }
 }
 
-impl TryFrom<UtcOffsetSpec> for icu_time::zone::UtcOffset {
-    type Error = crate::Error;
+/// Creates a date in the calendar of `spec` and converts it to ISO.
+fn date_from_fields(spec: &Spec, year: i32, day: u8) -> Result<Date<Iso>, crate::Error> {
+    let mut prefs = CalendarPreferences::default();
+    prefs.calendar_algorithm = Some(spec.calendar.unwrap_or(CalendarSerde::Iso8601).into());
+    let calendar = AnyCalendar::new(AnyCalendarKind::new(prefs));
 
-    fn try_from(value: UtcOffsetSpec) -> Result<Self, Self::Error> {
-        match value {
-            UtcOffsetSpec::OffsetSeconds(sec) => Self::try_from_seconds(sec),
-            UtcOffsetSpec::Chars(chars) => Self::try_from_str(&chars),
-        }
-        .map_err(|_| Self::Error::InvalidOffset)
+    let date = date_in(
+        calendar,
+        spec.era.as_deref(),
+        year,
+        spec.month_code.as_deref(),
+        spec.month,
+        day,
+    )?;
+    Ok(date.to_calendar(Iso))
+}
+
+/// Creates a date from its fields in `calendar`. If `era` is specified, `year`
+/// is the year in this era.
+pub fn date_in<A: AsCalendar>(
+    calendar: A,
+    era: Option<&str>,
+    year: i32,
+    month_code: Option<&str>,
+    month: Option<u8>,
+    day: u8,
+) -> Result<Date<A>, crate::Error> {
+    let mut fields = CalendarDateFields::default();
+    fields.era = era.map(str::as_bytes);
+    if fields.era.is_some() {
+        fields.era_year = Some(year);
+    } else {
+        fields.extended_year = Some(year);
     }
+    fields.month_code = month_code.map(str::as_bytes);
+    fields.ordinal_month = month;
+    fields.day = Some(day);
+
+    let mut options = DateFromFieldsOptions::default();
+    options.overflow = Some(Overflow::Reject);
+
+    Date::try_from_fields(fields, options, calendar).map_err(crate::Error::DateFields)
 }
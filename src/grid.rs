@@ -0,0 +1,206 @@
+//! Month grids (weeks × days) for calendars.
+
+use icu_calendar::{preferences::CalendarAlgorithm, week::WeekInformation, AsCalendar, Date, Iso};
+use icu_datetime::{
+    fieldsets::builder::{DateFields, FieldSetBuilder},
+    options::Length,
+    scaffold::CldrCalendar,
+    DateTimeFormatterPreferences,
+};
+use icu_locale_core::Locale;
+use icu_provider::DataProvider;
+use icu_time::ZonedDateTime;
+use serde::{Deserialize, Serialize};
+use writeable::Writeable;
+
+use crate::{calendar::with_calendar, format::SpecifiedZonedDateTime, week::WeekRules};
+
+/// The month of a grid (in the calendar of the grid).
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MonthSpec {
+    /// If specified, `year` is the year in this era.
+    era: Option<String>,
+    year: i32,
+    month: Option<u8>,
+    /// Alternative to `month` which can specify leap months (e.g. `M05L`).
+    month_code: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Header {
+    weekday: &'static str,
+    narrow: String,
+    short: String,
+    abbreviated: String,
+    wide: String,
+    is_weekend: bool,
+}
+
+#[derive(Serialize)]
+struct IsoDate {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Cell {
+    /// The day of the month in the calendar of the grid.
+    day: u8,
+    label: String,
+    /// `false` for the days of the previous and next month that fill the
+    /// first and last week.
+    in_month: bool,
+    date: IsoDate,
+}
+
+#[derive(Serialize)]
+struct Week {
+    /// The week of the year by the rules of the locale.
+    number: u8,
+    days: Vec<Cell>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Grid {
+    title: String,
+    year: i32,
+    month: u8,
+    month_code: String,
+    headers: Vec<Header>,
+    weeks: Vec<Week>,
+}
+
+/// Serializes the grid of `month` in `calendar` (or the calendar of
+/// `locale`). Weeks start on the first day of the locale.
+pub fn month(
+    month: MonthSpec,
+    locale: &str,
+    calendar: Option<CalendarAlgorithm>,
+) -> Result<Vec<u8>, crate::Error> {
+    if month.month.is_none() && month.month_code.is_none() {
+        return Err(crate::Error::PartialDate);
+    }
+
+    let locale = crate::data::locale(locale)?;
+    let mut prefs: DateTimeFormatterPreferences = (&locale).into();
+    if calendar.is_some() {
+        prefs.calendar_algorithm = calendar;
+    }
+    let algorithm = crate::calendar::resolve(&prefs)?;
+    prefs.calendar_algorithm = Some(algorithm);
+
+    let grid = with_calendar!(algorithm, |cal| grid_in(&month, &locale, prefs, cal))?;
+
+    let mut w = vec![];
+    ciborium::into_writer(&grid, &mut w)?;
+    Ok(w)
+}
+
+fn grid_in<C>(
+    month: &MonthSpec,
+    locale: &Locale,
+    prefs: DateTimeFormatterPreferences,
+    cal: C,
+) -> Result<Grid, crate::Error>
+where
+    C: AsCalendar<Calendar = C> + CldrCalendar + icu_calendar::Calendar + Clone + 'static,
+    icu_datetime::provider::Baked: DataProvider<<C as CldrCalendar>::YearNamesV1>,
+    icu_datetime::provider::Baked: DataProvider<<C as CldrCalendar>::MonthNamesV1>,
+    for<'a> crate::data::Deserializing<'a>: DataProvider<<C as CldrCalendar>::YearNamesV1>,
+    for<'a> crate::data::Deserializing<'a>: DataProvider<<C as CldrCalendar>::MonthNamesV1>,
+{
+    let first = crate::format::date_in(
+        cal.clone(),
+        month.era.as_deref(),
+        month.year,
+        month.month_code.as_deref(),
+        month.month,
+        1,
+    )?;
+    let week = WeekInformation::try_new(locale.into())?;
+    let rules = WeekRules::for_locale(locale, &week);
+
+    // the days before the first of the month in its first week
+    let leading = rules.position(first.day_of_week());
+    let end = leading + i32::from(first.days_in_month());
+    let start = first.to_rata_die() - i64::from(leading);
+    let days: Vec<_> = (0..(end + 6) / 7 * 7)
+        .map(|i| Date::from_rata_die(start + i64::from(i), Iso))
+        .collect();
+
+    let values: Vec<_> = days
+        .iter()
+        .map(|date| {
+            let value = SpecifiedZonedDateTime::from_date(*date).value;
+            ZonedDateTime {
+                date: date.to_calendar(cal.clone()),
+                time: value.time,
+                zone: value.zone,
+            }
+        })
+        .collect();
+    let format_all =
+        |pattern: &str, values: &[_]| crate::pattern::format_all(prefs.clone(), pattern, values);
+
+    let first_week = &values[..7];
+    let headers = days
+        .iter()
+        .zip(format_all("EEEEE", first_week)?)
+        .zip(format_all("EEEEEE", first_week)?)
+        .zip(format_all("EEE", first_week)?)
+        .zip(format_all("EEEE", first_week)?)
+        .map(|((((date, narrow), short), abbreviated), wide)| Header {
+            weekday: crate::week::code(date.day_of_week()),
+            narrow,
+            short,
+            abbreviated,
+            wide,
+            is_weekend: week.weekend.contains(date.day_of_week()),
+        })
+        .collect();
+
+    let mut cells = days
+        .iter()
+        .zip(&values)
+        .zip(format_all("d", &values)?)
+        .zip(0..)
+        .map(|(((date, value), label), i)| Cell {
+            day: value.date.day_of_month().0,
+            label,
+            in_month: (leading..end).contains(&i),
+            date: IsoDate {
+                year: date.extended_year(),
+                month: date.month().ordinal,
+                day: date.day_of_month().0,
+            },
+        });
+    let weeks = days
+        .chunks(7)
+        .map(|chunk| Week {
+            number: rules.week_of_year(chunk[0]).1,
+            days: cells.by_ref().take(7).collect(),
+        })
+        .collect();
+
+    let mut builder = FieldSetBuilder::new();
+    builder.length = Some(Length::Long);
+    builder.date_fields = Some(DateFields::YM);
+    let title = crate::cache::with_formatter(prefs, builder.build_composite()?, |formatter| {
+        let value = SpecifiedZonedDateTime::from_date(first.to_calendar(Iso)).value;
+        Ok(formatter.format(&value).write_to_string().into_owned())
+    })?;
+
+    Ok(Grid {
+        title,
+        year: first.extended_year(),
+        month: first.month().ordinal,
+        month_code: first.month().standard_code.0.to_string(),
+        headers,
+        weeks,
+    })
+}
@@ -1,10 +1,26 @@
 use icu_time::zone::{UtcOffset, VariantOffsets};
 use wasm_minimal_protocol::*;
 
+use write::Output;
+
+mod cache;
+mod calendar;
+mod collate;
+mod data;
+mod duration;
 mod format;
+mod grid;
+mod list;
 mod locale;
+mod number;
+mod parse;
 mod pattern;
+mod plural;
+mod relative;
 mod serde;
+mod symbols;
+mod tzdb;
+mod week;
 mod write;
 
 initiate_protocol!();
@@ -13,6 +29,8 @@ initiate_protocol!();
 pub enum Error {
     #[error("CBOR deserialization error for {0}: {1}")]
     De(&'static str, ciborium::de::Error<std::io::Error>),
+    #[error("CBOR deserialization error for {0}: {1}")]
+    DeValue(&'static str, String),
     #[error("CBOR serialization error: {0}")]
     Ser(#[from] ciborium::ser::Error<std::io::Error>),
     #[error("ICU locale error: {0}")]
@@ -30,16 +48,34 @@ pub enum Error {
     CompositeError(#[from] icu_datetime::fieldsets::builder::BuilderError),
     #[error("Invalid field range: {0}")]
     DateRange(icu_calendar::RangeError),
+    #[error("Invalid date: {0}")]
+    DateFields(icu_calendar::error::DateFromFieldsError),
     #[error("Invalid pattern: {0}")]
     PatternError(#[from] icu_datetime::provider::pattern::PatternError),
     #[error("Bad pattern: {0}")] // XXX: this looks like the error above
     PatternLoadError(#[from] icu_datetime::pattern::PatternLoadError),
     #[error("Failed to write: {0}")]
-    FormattedPatternError(icu_datetime::pattern::FormattedDateTimePatternError),
+    FormattedPatternError(#[from] icu_datetime::pattern::FormattedDateTimePatternError),
     #[error("Data error: {0}")]
     DataError(#[from] icu_provider::DataError),
+    #[error("Failed to load the data blob: {0}")]
+    LoadData(icu_provider::DataError),
+    #[error("The data for the locale '{0}' isn't included in this build of the plugin")]
+    ExcludedLocale(String),
+    #[error("There's no date/time data for the locale '{0}' (the data for '{1}' would be used)")]
+    MissingLocaleData(String, String),
+    #[error("Invalid decimal number: {0}")]
+    DecimalParse(#[from] fixed_decimal::ParseError),
+    #[error("The number can't be represented as a decimal: {0}")]
+    DecimalLimit(#[from] fixed_decimal::LimitError),
+    #[error("Invalid number of fraction digits in {0}: {1}")]
+    FractionDigits(&'static str, &'static str),
+    #[error("Invalid duration options: {0}")]
+    DurationOptions(icu_experimental::duration::options::DurationFormatterOptionsError),
+    #[error("All fields of a duration must have the same sign")]
+    MixedDurationSigns,
 
-    #[error("A partial date was provided - either year, month, and day must be provided or none")]
+    #[error("A partial date was provided - either year, month (or month-code), and day must be provided or none")]
     PartialDate,
     #[error("A partial time was provided - when specifying time, hour must always be present")]
     PartialTime,
@@ -52,9 +88,29 @@ pub enum Error {
     OffsetMismatch(InvalidVariantOffsets),
     #[error("Both IANA and Bcp47 IDs were specifies, expected at most one")]
     IanaAndBcp47,
+    #[error("Failed to parse the date/time string: {0}")]
+    Ixdtf(ixdtf::ParseError),
+    #[error("The calendar '{0}' is not supported")]
+    UnsupportedCalendar(String),
+    #[error("The calendar '{0}' isn't included in this build of the plugin")]
+    ExcludedCalendar(String),
+    #[error("Time zone names aren't included in this build of the plugin - only the localized-offset-* zone styles can be used")]
+    ExcludedZoneNames,
+    #[error("Failed to resolve the time zone offset: {0}")]
+    Tzdb(jiff::Error),
+    #[error("The local time doesn't exist in the time zone (it's skipped by a transition)")]
+    NonexistentTime,
+    #[error("The local time is ambiguous in the time zone (it's repeated by a transition)")]
+    AmbiguousTime,
 
     #[error("Some values are missing to format the date with the specified fields")]
     MissingValues,
+    #[error("Both ends of the range must be in the same time zone")]
+    RangeZoneMismatch,
+    #[error("Both ends of the range must be in the same calendar")]
+    RangeCalendarMismatch,
+    #[error("The start of the range must not be after its end")]
+    RangeOrder,
 }
 
 #[derive(Debug)]
@@ -68,7 +124,7 @@ impl std::fmt::Display for InvalidVariantOffsets {
             }
             write!(f, "{:02}", o.hours_part())?;
             let has_secs = o.seconds_part() != 0;
-            let has_mins = o.seconds_part() != 0;
+            let has_mins = o.minutes_part() != 0;
             if has_mins || has_secs {
                 write!(f, ":{:02}", o.minutes_part())?;
                 if has_secs {
@@ -91,22 +147,229 @@ impl std::fmt::Display for InvalidVariantOffsets {
 #[cfg_attr(target_arch = "wasm32", wasm_func)]
 pub fn format(spec: &[u8], locale: &[u8], opts: &[u8]) -> Result<Vec<u8>, Error> {
     let spec =
-        ciborium::from_reader::<format::Spec, _>(spec).map_err(|it| Error::De("spec", it))?;
+        ciborium::from_reader::<format::SpecInput, _>(spec).map_err(|it| Error::De("spec", it))?;
     let locale = std::str::from_utf8(locale)?;
     let builder = ciborium::from_reader::<serde::FieldSetBuilderSerde, _>(opts)
         .map_err(|it| Error::De("opts", it))?;
 
-    format::format(spec, &locale, builder.into())
+    if builder.strict {
+        locale::require_data(locale)?;
+    }
+    let calendar = builder.calendar.map(Into::into);
+
+    format::format(spec, &locale, builder.try_into()?, calendar, Output::Text)
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_func)]
-pub fn format_pattern(spec: &[u8], locale: &[u8], pattern: &[u8]) -> Result<Vec<u8>, Error> {
+pub fn format_to_parts(spec: &[u8], locale: &[u8], opts: &[u8]) -> Result<Vec<u8>, Error> {
     let spec =
-        ciborium::from_reader::<format::Spec, _>(spec).map_err(|it| Error::De("spec", it))?;
+        ciborium::from_reader::<format::SpecInput, _>(spec).map_err(|it| Error::De("spec", it))?;
+    let locale = std::str::from_utf8(locale)?;
+    let builder = ciborium::from_reader::<serde::FieldSetBuilderSerde, _>(opts)
+        .map_err(|it| Error::De("opts", it))?;
+
+    if builder.strict {
+        locale::require_data(locale)?;
+    }
+    let calendar = builder.calendar.map(Into::into);
+
+    format::format(spec, &locale, builder.try_into()?, calendar, Output::Parts)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn format_batch(specs: &[u8], locale: &[u8], opts: &[u8]) -> Result<Vec<u8>, Error> {
+    // the items are deserialized individually to report errors per item
+    let specs = ciborium::from_reader::<Vec<ciborium::Value>, _>(specs)
+        .map_err(|it| Error::De("specs", it))?;
+    let locale = std::str::from_utf8(locale)?;
+    let builder = ciborium::from_reader::<serde::FieldSetBuilderSerde, _>(opts)
+        .map_err(|it| Error::De("opts", it))?;
+
+    if builder.strict {
+        locale::require_data(locale)?;
+    }
+    let calendar = builder.calendar.map(Into::into);
+    let stats = builder.stats;
+
+    format::format_batch(specs, locale, builder.try_into()?, calendar, stats)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn format_range(from: &[u8], to: &[u8], locale: &[u8], opts: &[u8]) -> Result<Vec<u8>, Error> {
+    let from =
+        ciborium::from_reader::<format::SpecInput, _>(from).map_err(|it| Error::De("from", it))?;
+    let to = ciborium::from_reader::<format::SpecInput, _>(to).map_err(|it| Error::De("to", it))?;
+    let locale = std::str::from_utf8(locale)?;
+    let builder = ciborium::from_reader::<serde::FieldSetBuilderSerde, _>(opts)
+        .map_err(|it| Error::De("opts", it))?;
+
+    if builder.strict {
+        locale::require_data(locale)?;
+    }
+    let calendar = builder.calendar.map(Into::into);
+
+    format::format_range(from, to, locale, builder.try_into()?, calendar)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn format_relative(spec: &[u8], locale: &[u8], opts: &[u8]) -> Result<Vec<u8>, Error> {
+    let spec = ciborium::from_reader::<relative::RelativeSpec, _>(spec)
+        .map_err(|it| Error::De("spec", it))?;
+    let locale = std::str::from_utf8(locale)?;
+    let opts = ciborium::from_reader::<serde::RelativeTimeOptionsSerde, _>(opts)
+        .map_err(|it| Error::De("opts", it))?;
+
+    relative::format(spec, locale, opts)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn format_duration(duration: &[u8], locale: &[u8], opts: &[u8]) -> Result<Vec<u8>, Error> {
+    let duration = ciborium::from_reader::<duration::DurationSpec, _>(duration)
+        .map_err(|it| Error::De("duration", it))?;
+    let locale = std::str::from_utf8(locale)?;
+    let opts = ciborium::from_reader::<serde::DurationOptionsSerde, _>(opts)
+        .map_err(|it| Error::De("opts", it))?;
+
+    duration::format(duration, locale, opts)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn format_pattern(
+    spec: &[u8],
+    locale: &[u8],
+    pattern: &[u8],
+    opts: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let spec =
+        ciborium::from_reader::<format::SpecInput, _>(spec).map_err(|it| Error::De("spec", it))?;
     let locale = std::str::from_utf8(locale)?;
     let pattern = std::str::from_utf8(pattern)?;
+    let opts = ciborium::from_reader::<serde::PatternOptionsSerde, _>(opts)
+        .map_err(|it| Error::De("opts", it))?;
+
+    if opts.strict {
+        locale::require_data(locale)?;
+    }
+
+    pattern::format(
+        pattern,
+        &locale,
+        spec,
+        opts.calendar.map(Into::into),
+        Output::Text,
+    )
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn format_pattern_to_parts(
+    spec: &[u8],
+    locale: &[u8],
+    pattern: &[u8],
+    opts: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let spec =
+        ciborium::from_reader::<format::SpecInput, _>(spec).map_err(|it| Error::De("spec", it))?;
+    let locale = std::str::from_utf8(locale)?;
+    let pattern = std::str::from_utf8(pattern)?;
+    let opts = ciborium::from_reader::<serde::PatternOptionsSerde, _>(opts)
+        .map_err(|it| Error::De("opts", it))?;
+
+    if opts.strict {
+        locale::require_data(locale)?;
+    }
+
+    pattern::format(
+        pattern,
+        &locale,
+        spec,
+        opts.calendar.map(Into::into),
+        Output::Parts,
+    )
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn to_calendar(spec: &[u8], calendar: &[u8]) -> Result<Vec<u8>, Error> {
+    let spec =
+        ciborium::from_reader::<format::SpecInput, _>(spec).map_err(|it| Error::De("spec", it))?;
+    let calendar = ciborium::from_reader::<serde::CalendarSerde, _>(calendar)
+        .map_err(|it| Error::De("calendar", it))?;
+
+    calendar::convert(spec, calendar)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn week_info(locale: &[u8], spec: &[u8]) -> Result<Vec<u8>, Error> {
+    let locale = std::str::from_utf8(locale)?;
+    let spec = ciborium::from_reader::<Option<format::SpecInput>, _>(spec)
+        .map_err(|it| Error::De("spec", it))?;
 
-    pattern::format(pattern, &locale, spec)
+    week::info(locale, spec)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn month_grid(month: &[u8], locale: &[u8], calendar: &[u8]) -> Result<Vec<u8>, Error> {
+    let month =
+        ciborium::from_reader::<grid::MonthSpec, _>(month).map_err(|it| Error::De("month", it))?;
+    let locale = std::str::from_utf8(locale)?;
+    let calendar = ciborium::from_reader::<Option<serde::CalendarSerde>, _>(calendar)
+        .map_err(|it| Error::De("calendar", it))?;
+
+    grid::month(month, locale, calendar.map(Into::into))
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn date_symbols(locale: &[u8], calendar: &[u8], year: &[u8]) -> Result<Vec<u8>, Error> {
+    let locale = std::str::from_utf8(locale)?;
+    let calendar = ciborium::from_reader::<Option<serde::CalendarSerde>, _>(calendar)
+        .map_err(|it| Error::De("calendar", it))?;
+    let year = ciborium::from_reader::<Option<i32>, _>(year).map_err(|it| Error::De("year", it))?;
+
+    symbols::names(locale, calendar.map(Into::into), year)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn format_number(number: &[u8], locale: &[u8], opts: &[u8]) -> Result<Vec<u8>, Error> {
+    let number = ciborium::from_reader::<number::NumberSpec, _>(number)
+        .map_err(|it| Error::De("number", it))?;
+    let locale = std::str::from_utf8(locale)?;
+    let opts = ciborium::from_reader::<serde::DecimalOptionsSerde, _>(opts)
+        .map_err(|it| Error::De("opts", it))?;
+
+    number::format(number, locale, opts)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn format_list(items: &[u8], locale: &[u8], opts: &[u8]) -> Result<Vec<u8>, Error> {
+    let items =
+        ciborium::from_reader::<Vec<String>, _>(items).map_err(|it| Error::De("items", it))?;
+    let locale = std::str::from_utf8(locale)?;
+    let opts = ciborium::from_reader::<serde::ListOptionsSerde, _>(opts)
+        .map_err(|it| Error::De("opts", it))?;
+
+    list::format(items, locale, opts)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn sort(items: &[u8], locale: &[u8], opts: &[u8]) -> Result<Vec<u8>, Error> {
+    let items =
+        ciborium::from_reader::<Vec<String>, _>(items).map_err(|it| Error::De("items", it))?;
+    let locale = std::str::from_utf8(locale)?;
+    let opts = ciborium::from_reader::<serde::CollatorOptionsSerde, _>(opts)
+        .map_err(|it| Error::De("opts", it))?;
+
+    collate::sort(items, locale, opts)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn load_data(blob: &[u8]) -> Result<Vec<u8>, Error> {
+    data::load(blob)?;
+
+    Ok(vec![])
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn cache_stats() -> Result<Vec<u8>, Error> {
+    cache::stats()
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_func)]
@@ -115,3 +378,55 @@ pub fn locale_info(locale: &[u8]) -> Result<Vec<u8>, Error> {
 
     locale::info(locale)
 }
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn locale_canonicalize(locale: &[u8]) -> Result<Vec<u8>, Error> {
+    let locale = std::str::from_utf8(locale)?;
+
+    locale::transform(locale, locale::Transform::Canonicalize)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn locale_maximize(locale: &[u8]) -> Result<Vec<u8>, Error> {
+    let locale = std::str::from_utf8(locale)?;
+
+    locale::transform(locale, locale::Transform::Maximize)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn locale_minimize(locale: &[u8]) -> Result<Vec<u8>, Error> {
+    let locale = std::str::from_utf8(locale)?;
+
+    locale::transform(locale, locale::Transform::Minimize)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn locale_fallback(locale: &[u8]) -> Result<Vec<u8>, Error> {
+    let locale = std::str::from_utf8(locale)?;
+
+    locale::fallback(locale)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn locale_display_names(
+    locale: &[u8],
+    display_locale: &[u8],
+    opts: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let locale = std::str::from_utf8(locale)?;
+    let display_locale = std::str::from_utf8(display_locale)?;
+    let opts = ciborium::from_reader::<serde::DisplayNamesOptionsSerde, _>(opts)
+        .map_err(|it| Error::De("opts", it))?;
+
+    locale::display_names(locale, display_locale, opts)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_func)]
+pub fn plural_category(number: &[u8], locale: &[u8], rule_type: &[u8]) -> Result<Vec<u8>, Error> {
+    let number = std::str::from_utf8(number)?;
+    let locale = std::str::from_utf8(locale)?;
+    let rule_type = ciborium::from_reader::<serde::PluralRuleTypeSerde, _>(rule_type)
+        .map_err(|it| Error::De("rule_type", it))?;
+
+    plural::category(number, locale, rule_type)
+}
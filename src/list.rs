@@ -0,0 +1,24 @@
+use icu_list::{options::ListFormatterOptions, ListFormatter};
+
+use crate::serde::{ListKindSerde, ListOptionsSerde};
+
+pub fn format(
+    items: Vec<String>,
+    locale: &str,
+    opts: ListOptionsSerde,
+) -> Result<Vec<u8>, crate::Error> {
+    let prefs = crate::data::locale(locale)?.into();
+
+    let mut options = ListFormatterOptions::default();
+    options.length = opts.width.map(Into::into);
+
+    let formatter = match opts.kind.unwrap_or_default() {
+        ListKindSerde::And => ListFormatter::try_new_and(prefs, options)?,
+        ListKindSerde::Or => ListFormatter::try_new_or(prefs, options)?,
+        ListKindSerde::Unit => ListFormatter::try_new_unit(prefs, options)?,
+    };
+
+    Ok(crate::write::to_vec(
+        formatter.format(items.iter().map(String::as_str)),
+    ))
+}
@@ -1,16 +1,371 @@
 use std::str::FromStr;
 
+use icu_calendar::{cal::Gregorian, preferences::CalendarPreferences, week::WeekInformation};
+use icu_datetime::{fieldsets, scaffold::CldrCalendar, NoCalendarFormatter};
+use icu_decimal::provider::DecimalSymbolsV1;
+use icu_experimental::displaynames::{
+    DisplayNamesOptions, LanguageDisplayNames, LocaleDisplayNamesFormatter, RegionDisplayNames,
+    ScriptDisplayNames, VariantDisplayNames,
+};
+use icu_locale::{LocaleCanonicalizer, LocaleExpander, LocaleFallbacker};
+use icu_locale_core::{
+    extensions::unicode::{key, Key},
+    subtags::Region,
+    LanguageIdentifier,
+};
+use icu_provider::{
+    DataIdentifierBorrowed, DataLocale, DataMarker, DataProvider, DataRequest, DataResponse,
+};
+use icu_provider_adapters::fallback::LocaleFallbackProvider;
+use icu_time::Time;
+use serde::Serialize;
+
+use crate::serde::DisplayNamesOptionsSerde;
+
 pub fn info(name: &str) -> Result<Vec<u8>, crate::Error> {
-    let locale = wrap::Locale::from(icu_locale_core::Locale::from_str(name)?);
+    serialize_info(icu_locale_core::Locale::from_str(name)?)
+}
+
+/// A locale and the preferences that result from it.
+#[derive(Serialize)]
+struct Info {
+    #[serde(flatten)]
+    locale: wrap::Locale,
+    preferences: Preferences,
+}
+
+/// The preferences of a locale - either set explicitly through `-u-`
+/// extensions or the defaults of the locale and its (likely) region.
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Preferences {
+    /// The region that regional preferences are taken from (`-u-rg` or the
+    /// likely region).
+    region: Option<String>,
+    hour_cycle: Option<&'static str>,
+    calendar: String,
+    first_day: &'static str,
+    numbering_system: String,
+    measurement_system: String,
+}
+
+fn serialize_info(locale: icu_locale_core::Locale) -> Result<Vec<u8>, crate::Error> {
+    let info = Info {
+        preferences: preferences(&locale)?,
+        locale: locale.into(),
+    };
+
     let mut w = vec![];
-    ciborium::into_writer(&locale, &mut w)?;
+    ciborium::into_writer(&info, &mut w)?;
+
+    Ok(w)
+}
+
+fn preferences(locale: &icu_locale_core::Locale) -> Result<Preferences, crate::Error> {
+    let keyword = |key: Key| {
+        locale
+            .extensions
+            .unicode
+            .keywords
+            .get(&key)
+            .map(|it| it.to_string())
+    };
+
+    let region = region(locale);
+    let first_day = crate::week::code(WeekInformation::try_new(locale.into())?.first_weekday);
+
+    let numbering_system = match keyword(key!("nu")) {
+        Some(nu) => nu,
+        None => {
+            let provider = LocaleFallbackProvider::new(
+                icu_decimal::provider::Baked,
+                LocaleFallbacker::new().static_to_owned(),
+            );
+            let symbols: DataResponse<DecimalSymbolsV1> = provider.load(DataRequest {
+                id: DataIdentifierBorrowed::for_locale(&DataLocale::from(locale)),
+                ..Default::default()
+            })?;
+            symbols.payload.get().numsys().to_owned()
+        }
+    };
+
+    // ICU4X doesn't expose CLDR's measurementData, so this is a copy of it
+    // (CLDR 48) that has to be updated by hand. It only has the main system
+    // per region and ignores the per-category overrides (e.g. temperature).
+    // See measurementData in https://github.com/unicode-org/cldr/blob/main/common/supplemental/supplementalData.xml
+    let measurement_system = keyword(key!("ms")).unwrap_or_else(|| {
+        match region.as_deref() {
+            Some("US" | "LR" | "MM") => "ussystem",
+            Some("GB") => "uksystem",
+            _ => "metric",
+        }
+        .to_owned()
+    });
+
+    Ok(Preferences {
+        hour_cycle: hour_cycle(locale)?,
+        calendar: crate::calendar::name(CalendarPreferences::from(locale).resolved_algorithm()),
+        first_day,
+        numbering_system,
+        measurement_system,
+        region,
+    })
+}
+
+/// Gets the region that regional preferences of `locale` are taken from
+/// (`-u-rg`, the region of the locale, or its likely region).
+pub fn region(locale: &icu_locale_core::Locale) -> Option<String> {
+    match locale.extensions.unicode.keywords.get(&key!("rg")) {
+        // A region (two letters or three digits) followed by a subdivision
+        // suffix, e.g. "usxxxx", "czzzzz", or "001zzzz".
+        Some(rg) => {
+            let rg = rg.to_string();
+            let len = if rg.starts_with(|c: char| c.is_ascii_digit()) {
+                3
+            } else {
+                2
+            };
+            rg.get(..len)
+                .and_then(|it| Region::try_from_str(it).ok())
+                .map(|it| it.to_string())
+        }
+        None => {
+            let mut id = locale.id.clone();
+            LocaleExpander::new_extended().maximize(&mut id);
+            id.region.map(|it| it.to_string())
+        }
+    }
+}
+
+/// Gets the hour cycle of the time formats in `locale` (`-u-hc` or the
+/// default of the locale).
+fn hour_cycle(locale: &icu_locale_core::Locale) -> Result<Option<&'static str>, crate::Error> {
+    let formatter = NoCalendarFormatter::try_new(locale.into(), fieldsets::T::short())
+        .map_err(crate::Error::LoadDateTimeFormatter)?;
+    let time = Time::try_new(13, 0, 0, 0).map_err(crate::Error::DateRange)?;
+    let pattern = formatter.format(&time).pattern().to_string();
+
+    // the first hour field outside of quoted literals
+    let mut quoted = false;
+    for c in pattern.chars() {
+        match c {
+            '\'' => quoted = !quoted,
+            'h' if !quoted => return Ok(Some("h12")),
+            'H' if !quoted => return Ok(Some("h23")),
+            'K' if !quoted => return Ok(Some("h11")),
+            'k' if !quoted => return Ok(Some("h24")),
+            _ => (),
+        }
+    }
+    Ok(None)
+}
+
+/// A transformation of a locale (see [`transform`]).
+#[derive(Clone, Copy)]
+pub enum Transform {
+    /// Resolves aliases (e.g. `iw` to `he`) and normalizes the casing.
+    Canonicalize,
+    /// Adds the likely subtags (e.g. `zh-TW` to `zh-Hant-TW`).
+    Maximize,
+    /// Removes the subtags that would be added by [`Transform::Maximize`]
+    /// (e.g. `zh-Hant-TW` to `zh-TW`).
+    Minimize,
+}
+
+/// Transforms the locale `name` and serializes it like [`info`] (the
+/// preferences are resolved for the transformed locale).
+///
+/// The locale is always canonicalized, so aliases are resolved before adding
+/// or removing subtags.
+pub fn transform(name: &str, transform: Transform) -> Result<Vec<u8>, crate::Error> {
+    let mut locale = icu_locale_core::Locale::from_str(name)?;
+    LocaleCanonicalizer::new_extended().canonicalize(&mut locale);
+
+    let expander = LocaleExpander::new_extended();
+    match transform {
+        Transform::Canonicalize => (),
+        Transform::Maximize => {
+            expander.maximize(&mut locale.id);
+        }
+        Transform::Minimize => {
+            expander.minimize(&mut locale.id);
+        }
+    }
+
+    serialize_info(locale)
+}
+
+/// A locale in the fallback chain.
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct FallbackStep {
+    locale: String,
+    has_data: bool,
+    #[serde(skip)]
+    data_locale: DataLocale,
+}
+
+#[derive(Serialize)]
+struct Fallback {
+    requested: String,
+    resolved: String,
+    exact: bool,
+    chain: Vec<FallbackStep>,
+}
+
+/// The marker that represents the date/time data of a locale.
+type DateTimeMarker = <Gregorian as CldrCalendar>::MonthNamesV1;
+
+/// Walks the fallback chain of `locale` for the date/time data and checks
+/// which locales have data. The chain ends with `und` (the root locale).
+fn fallback_chain(locale: &icu_locale_core::Locale) -> Result<Vec<FallbackStep>, crate::Error> {
+    let mut iter = LocaleFallbacker::new()
+        .for_config(DateTimeMarker::INFO.fallback_config)
+        .fallback_for(DataLocale::from(locale));
+
+    let mut chain = vec![];
+    loop {
+        let current = iter.get();
+        chain.push(FallbackStep {
+            locale: current.to_string(),
+            has_data: crate::data::has_data::<DateTimeMarker>(current)?,
+            data_locale: *current,
+        });
+        if *current == DataLocale::default() {
+            return Ok(chain);
+        }
+        iter.step();
+    }
+}
+
+/// The locale that the date/time data is taken from.
+fn resolved(chain: &[FallbackStep]) -> String {
+    chain
+        .iter()
+        .find(|it| it.has_data)
+        .map_or_else(|| "und".to_owned(), |it| it.locale.clone())
+}
+
+/// Checks if the date/time data that's used for the first locale in `chain`
+/// is the data of the locale itself.
+///
+/// This is the case if the locale has data or if the data is from a locale
+/// that's equivalent by the likely subtags (e.g. "en" for "en-US" or "de" for
+/// "de-DE"). The data isn't duplicated for such locales.
+fn is_exact(chain: &[FallbackStep]) -> bool {
+    if chain[0].has_data {
+        return true;
+    }
+    let requested = &chain[0].data_locale;
+    let Some(resolved) = chain
+        .iter()
+        .find(|it| it.has_data)
+        .map(|it| &it.data_locale)
+    else {
+        return false;
+    };
+
+    let expander = LocaleExpander::new_extended();
+    let maximized = |locale: &DataLocale| {
+        let mut id = LanguageIdentifier::UNKNOWN;
+        id.language = locale.language;
+        id.script = locale.script;
+        id.region = locale.region;
+        expander.maximize(&mut id);
+        id
+    };
+    // there's no data for subdivisions (`-u-sd`), so they're ignored
+    maximized(requested) == maximized(resolved) && requested.variant == resolved.variant
+}
+
+/// Serializes the fallback chain of `name` for the date/time data.
+pub fn fallback(name: &str) -> Result<Vec<u8>, crate::Error> {
+    let locale = icu_locale_core::Locale::from_str(name)?;
+    let chain = fallback_chain(&locale)?;
+    let fallback = Fallback {
+        requested: chain[0].locale.clone(),
+        resolved: resolved(&chain),
+        exact: is_exact(&chain),
+        chain,
+    };
+
+    let mut w = vec![];
+    ciborium::into_writer(&fallback, &mut w)?;
+
+    Ok(w)
+}
+
+/// Checks that there's date/time data for `name` itself (as opposed to one
+/// of its parents, see [`is_exact`]).
+pub fn require_data(name: &str) -> Result<(), crate::Error> {
+    let locale = icu_locale_core::Locale::from_str(name)?;
+    let chain = fallback_chain(&locale)?;
+    if is_exact(&chain) {
+        Ok(())
+    } else {
+        Err(crate::Error::MissingLocaleData(
+            chain[0].locale.clone(),
+            resolved(&chain),
+        ))
+    }
+}
+
+/// Localized names of a locale and its components.
+#[derive(Serialize)]
+struct DisplayNames<'a> {
+    locale: &'a str,
+    language: Option<&'a str>,
+    script: Option<&'a str>,
+    region: Option<&'a str>,
+    variants: Vec<Option<&'a str>>,
+}
+
+pub fn display_names(
+    name: &str,
+    display_locale: &str,
+    opts: DisplayNamesOptionsSerde,
+) -> Result<Vec<u8>, crate::Error> {
+    let locale = icu_locale_core::Locale::from_str(name)?;
+    let display_locale = crate::data::locale(display_locale)?;
+
+    let options = || {
+        let mut options = DisplayNamesOptions::default();
+        options.style = opts.style.map(Into::into);
+        if let Some(language_display) = opts.language_display {
+            options.language_display = language_display.into();
+        }
+        options
+    };
+
+    let locale_names =
+        LocaleDisplayNamesFormatter::try_new(display_locale.clone().into(), options())?;
+    let language_names = LanguageDisplayNames::try_new(display_locale.clone().into(), options())?;
+    let script_names = ScriptDisplayNames::try_new(display_locale.clone().into(), options())?;
+    let region_names = RegionDisplayNames::try_new(display_locale.clone().into(), options())?;
+    let variant_names = VariantDisplayNames::try_new(display_locale.into(), options())?;
+
+    let locale_name = locale_names.of(&locale);
+    let id = &locale.id;
+    let names = DisplayNames {
+        locale: &locale_name,
+        language: language_names.of(id.language),
+        script: id.script.and_then(|it| script_names.of(it)),
+        region: id.region.and_then(|it| region_names.of(it)),
+        variants: id.variants.iter().map(|it| variant_names.of(*it)).collect(),
+    };
+
+    let mut w = vec![];
+    ciborium::into_writer(&names, &mut w)?;
 
     Ok(w)
 }
 
 /// This module wraps [icu_locale_core::Locale] to be able to serialize it.
 mod wrap {
-    use serde::{ser::SerializeSeq, Serialize};
+    use serde::{
+        ser::{SerializeMap, SerializeSeq},
+        Serialize,
+    };
 
     #[derive(Serialize)]
     pub struct Locale {
@@ -67,7 +422,7 @@ mod wrap {
 
     #[derive(Serialize)]
     pub struct Unicode {
-        pub keywords: Displayer<icu_locale_core::extensions::unicode::Keywords>,
+        pub keywords: KeywordMap,
         pub attributes: Slicer<icu_locale_core::extensions::unicode::Attributes>,
     }
 
@@ -80,6 +435,32 @@ mod wrap {
         }
     }
 
+    /// Serializes the keywords as a map from the key to the list of subtags
+    /// (e.g. `{"ca": ["islamic", "civil"]}`).
+    #[repr(transparent)]
+    pub struct KeywordMap(pub icu_locale_core::extensions::unicode::Keywords);
+
+    impl Serialize for KeywordMap {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let mut map = serializer.serialize_map(None)?;
+            for (key, value) in self.0.iter() {
+                let value = value.to_string();
+                let subtags = value.split('-').filter(|it| !it.is_empty());
+                map.serialize_entry(key.as_str(), &subtags.collect::<Vec<_>>())?;
+            }
+            map.end()
+        }
+    }
+
+    impl From<icu_locale_core::extensions::unicode::Keywords> for KeywordMap {
+        fn from(value: icu_locale_core::extensions::unicode::Keywords) -> Self {
+            Self(value)
+        }
+    }
+
     #[derive(Serialize)]
     pub struct Transform {
         pub lang: Option<LanguageIdentifier>,
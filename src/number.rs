@@ -0,0 +1,62 @@
+use fixed_decimal::{Decimal, FloatPrecision};
+use icu_decimal::{options::DecimalFormatterOptions, DecimalFormatter};
+use serde::Deserialize;
+
+use crate::serde::DecimalOptionsSerde;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum NumberSpec {
+    Integer(i64),
+    Float(f64),
+    /// A decimal number as a string to retain its precision (e.g. `"1.50"`).
+    Decimal(String),
+}
+
+pub fn format(
+    number: NumberSpec,
+    locale: &str,
+    opts: DecimalOptionsSerde,
+) -> Result<Vec<u8>, crate::Error> {
+    let prefs = crate::data::locale(locale)?.into();
+    let mut value = Decimal::try_from(number)?;
+
+    let (min, max) = (opts.min_fraction_digits, opts.max_fraction_digits);
+    if min.is_some_and(|it| it < 0)
+        || max.is_some_and(|it| it < 0)
+        || min.zip(max).is_some_and(|(min, max)| min > max)
+    {
+        return Err(crate::Error::FractionDigits(
+            "the number options",
+            "the minimum and maximum must not be negative and the minimum must not exceed the maximum",
+        ));
+    }
+
+    if let Some(max) = max {
+        value.round_with_mode(-max, opts.rounding_mode.unwrap_or_default().into());
+    }
+    if let Some(min) = min {
+        value.pad_end(-min);
+    }
+    if let Some(sign_display) = opts.sign_display {
+        value.apply_sign_display(sign_display.into());
+    }
+
+    let mut options = DecimalFormatterOptions::default();
+    options.grouping_strategy = opts.grouping.map(Into::into);
+    let formatter = DecimalFormatter::try_new(prefs, options)?;
+
+    Ok(crate::write::to_vec(formatter.format(&value)))
+}
+
+impl TryFrom<NumberSpec> for Decimal {
+    type Error = crate::Error;
+
+    fn try_from(value: NumberSpec) -> Result<Self, Self::Error> {
+        Ok(match value {
+            NumberSpec::Integer(int) => Self::from(int),
+            NumberSpec::Float(float) => Self::try_from_f64(float, FloatPrecision::RoundTrip)?,
+            NumberSpec::Decimal(str) => Self::try_from_str(&str)?,
+        })
+    }
+}
@@ -0,0 +1,154 @@
+//! Parsing of IXDTF strings ([RFC 9557]) into [`Spec`]s.
+//!
+//! [RFC 9557]: https://www.rfc-editor.org/rfc/rfc9557
+
+use icu_calendar::preferences::CalendarAlgorithm;
+use icu_locale_core::extensions::unicode::Value;
+use ixdtf::{
+    parsers::IxdtfParser,
+    records::{IxdtfParseRecord, TimeRecord, TimeZoneRecord, UtcOffsetRecordOrZ},
+};
+
+use crate::format::{Spec, TimezoneSpec, UtcOffsetSpec};
+
+/// Parses a date, a date with a time, or a time, each optionally followed by
+/// an offset and annotations (e.g. `2025-03-14T09:30:00+01:00[Europe/Berlin][u-ca=hebrew]`).
+pub fn spec_from_ixdtf(s: &str) -> Result<Spec, crate::Error> {
+    let record = match IxdtfParser::from_str(s).parse() {
+        Ok(record) => record,
+        // a time on its own is only accepted if it's not a date
+        Err(e) => IxdtfParser::from_str(s)
+            .parse_time()
+            .map_err(|_| crate::Error::Ixdtf(e))?,
+    };
+
+    let calendar_annotation = match record.calendar {
+        Some(name) => parse_calendar(name)?,
+        None => None,
+    };
+    let zone = parse_zone(&record)?;
+
+    let mut spec = Spec {
+        year: None,
+        month: None,
+        day: None,
+        hour: None,
+        minute: None,
+        second: None,
+        nanosecond: None,
+        zone: None,
+        calendar: None,
+        era: None,
+        month_code: None,
+        calendar_annotation,
+    };
+    if let Some(date) = record.date {
+        spec.year = Some(date.year);
+        spec.month = Some(date.month);
+        spec.day = Some(date.day);
+    }
+    if let Some(time) = record.time {
+        set_time(&mut spec, &time)?;
+    }
+
+    match zone {
+        // `Z` with a time zone only specifies the instant - the local time has
+        // to be derived from the zone.
+        Some((offset, Some(iana))) if offset.is_none() && is_utc_designator(&record) => {
+            let utc = jiff::civil::DateTime::new(
+                spec.year.unwrap_or(1970).try_into().unwrap_or(i16::MAX),
+                spec.month.unwrap_or(1) as i8,
+                spec.day.unwrap_or(1) as i8,
+                spec.hour.unwrap_or_default() as i8,
+                spec.minute.unwrap_or_default() as i8,
+                spec.second.unwrap_or_default() as i8,
+                spec.nanosecond.unwrap_or_default() as i32,
+            )
+            .map_err(crate::Error::Tzdb)?;
+            let (local, offset) = crate::tzdb::utc_to_local(&iana, utc)?;
+            if spec.year.is_some() {
+                spec.year = Some(local.year().into());
+                spec.month = Some(local.month() as u8);
+                spec.day = Some(local.day() as u8);
+            }
+            if spec.hour.is_some() {
+                spec.hour = Some(local.hour() as u8);
+                spec.minute = Some(local.minute() as u8);
+                spec.second = Some(local.second() as u8);
+            }
+            spec.zone = Some(TimezoneSpec::new(
+                Some(UtcOffsetSpec::OffsetSeconds(offset)),
+                Some(iana),
+            ));
+        }
+        Some((offset, iana)) => spec.zone = Some(TimezoneSpec::new(offset, iana)),
+        None => (),
+    }
+
+    Ok(spec)
+}
+
+/// Gets the offset and the IANA name of the zone. `Z` is only reported as an
+/// offset if there's no named zone.
+fn parse_zone(
+    record: &IxdtfParseRecord<'_, ixdtf::encoding::Utf8>,
+) -> Result<Option<(Option<UtcOffsetSpec>, Option<String>)>, crate::Error> {
+    let iana = match record.tz.as_ref().map(|it| &it.tz) {
+        Some(TimeZoneRecord::Name(name)) => Some(std::str::from_utf8(name)?.to_owned()),
+        Some(TimeZoneRecord::Offset(offset)) => {
+            let seconds =
+                (offset.hour as i32 * 3600 + offset.minute as i32 * 60) * offset.sign as i32;
+            return Ok(Some((Some(UtcOffsetSpec::OffsetSeconds(seconds)), None)));
+        }
+        _ => None,
+    };
+
+    let offset = match record.offset {
+        Some(UtcOffsetRecordOrZ::Offset(offset)) => {
+            let seconds = offset.hour() as i32 * 3600
+                + offset.minute() as i32 * 60
+                + offset.second().unwrap_or_default() as i32;
+            Some(UtcOffsetSpec::OffsetSeconds(seconds * offset.sign() as i32))
+        }
+        Some(UtcOffsetRecordOrZ::Z) if iana.is_none() => Some(UtcOffsetSpec::OffsetSeconds(0)),
+        _ => None,
+    };
+
+    Ok(match (offset, iana) {
+        (None, None) => None,
+        (offset, iana) => Some((offset, iana)),
+    })
+}
+
+fn is_utc_designator(record: &IxdtfParseRecord<'_, ixdtf::encoding::Utf8>) -> bool {
+    matches!(record.offset, Some(UtcOffsetRecordOrZ::Z))
+}
+
+fn set_time(spec: &mut Spec, time: &TimeRecord) -> Result<(), crate::Error> {
+    spec.hour = Some(time.hour);
+    spec.minute = Some(time.minute);
+    // leap seconds are clamped like in Temporal
+    spec.second = Some(time.second.min(59));
+    spec.nanosecond = time
+        .fraction
+        .map(|it| {
+            it.to_nanoseconds().ok_or(crate::Error::FractionDigits(
+                "the seconds of the time",
+                "there can be at most 9 digits",
+            ))
+        })
+        .transpose()?;
+    Ok(())
+}
+
+/// Parses the `u-ca` annotation. `iso8601` is treated like no annotation:
+/// it's the calendar of the fields themselves and is commonly added to
+/// RFC 9557 output, but it isn't a calendar that dates are formatted in.
+fn parse_calendar(name: &[u8]) -> Result<Option<CalendarAlgorithm>, crate::Error> {
+    let unsupported = || crate::Error::UnsupportedCalendar(String::from_utf8_lossy(name).into());
+    let value = Value::try_from_utf8(name).map_err(|_| unsupported())?;
+    match CalendarAlgorithm::try_from(&value).map_err(|_| unsupported())? {
+        CalendarAlgorithm::Iso8601 => Ok(None),
+        algorithm => Ok(Some(algorithm)),
+    }
+}
@@ -1,112 +1,156 @@
-use std::str::FromStr;
+use std::borrow::Cow;
 
-use icu_calendar::{
-    cal::{ChineseTraditional, KoreanTraditional},
-    preferences::{CalendarAlgorithm, CalendarPreferences, HijriCalendarAlgorithm},
-    AsCalendar,
-};
+use icu_calendar::{preferences::CalendarAlgorithm, AsCalendar};
 use icu_datetime::{
     fieldsets::enums::CompositeFieldSet,
     pattern::{DateTimePattern, FixedCalendarDateTimeNames},
     scaffold::CldrCalendar,
     DateTimeFormatterPreferences,
 };
-use icu_locale_core::Locale;
-use icu_provider::DataProvider;
-use icu_time::ZonedDateTime;
+use icu_provider::{buf::AsDeserializingBufferProvider, DataProvider};
+use icu_time::{zone::models::AtTime, TimeZoneInfo, ZonedDateTime};
+use writeable::TryWriteable;
 
-use crate::format::{Spec, SpecifiedZonedDateTime};
+use crate::{
+    calendar::with_calendar,
+    format::{SpecInput, SpecifiedZonedDateTime},
+    write::Output,
+};
 
-pub fn format(pattern_str: &str, locale: &str, spec: Spec) -> Result<Vec<u8>, crate::Error> {
+pub fn format(
+    pattern_str: &str,
+    locale: &str,
+    spec: SpecInput,
+    calendar: Option<CalendarAlgorithm>,
+    output: Output,
+) -> Result<Vec<u8>, crate::Error> {
     let spec: SpecifiedZonedDateTime = spec.try_into()?;
 
-    let prefs = Locale::from_str(locale)?.into();
-    let pattern = DateTimePattern::try_from_pattern_str(pattern_str)?;
+    let locale = crate::data::locale(locale)?;
+    let prefs = spec.preferences(&locale, calendar)?;
 
     // waiting on https://github.com/unicode-org/icu4x/issues/6107
-    format_with_calendar(&spec, prefs, &pattern)
+    with_calendar!(crate::calendar::resolve(&prefs)?, |cal| fmt_impl(
+        &spec,
+        prefs,
+        pattern_str,
+        cal,
+        output
+    ))
 }
 
-fn format_with_calendar(
-    spec: &SpecifiedZonedDateTime,
-    mut prefs: DateTimeFormatterPreferences,
-    pattern: &DateTimePattern,
-) -> Result<Vec<u8>, crate::Error> {
-    use icu_calendar::cal::{
-        hijri, Buddhist, Coptic, Ethiopian, EthiopianEraStyle, Gregorian, Hebrew, Hijri,
-        HijriTabularEpoch, HijriTabularLeapYears, Indian, Japanese, Persian, Roc,
-    };
-    // https://github.com/unicode-org/icu4x/blob/icu%402.2.0/components/datetime/src/scaffold/calendar.rs#L449-L488
-    match CalendarPreferences::from(&prefs).resolved_algorithm() {
-        CalendarAlgorithm::Buddhist => fmt_impl(spec, prefs, pattern, Buddhist),
-        CalendarAlgorithm::Chinese => fmt_impl(spec, prefs, pattern, ChineseTraditional::new()),
-        CalendarAlgorithm::Coptic => fmt_impl(spec, prefs, pattern, Coptic),
-        CalendarAlgorithm::Dangi => fmt_impl(spec, prefs, pattern, KoreanTraditional::new()),
-        CalendarAlgorithm::Ethiopic => fmt_impl(spec, prefs, pattern, Ethiopian::new()),
-        CalendarAlgorithm::Ethioaa => fmt_impl(
-            spec,
-            prefs,
-            pattern,
-            Ethiopian::new_with_era_style(EthiopianEraStyle::AmeteAlem),
-        ),
-        CalendarAlgorithm::Gregory => fmt_impl(spec, prefs, pattern, Gregorian),
-        CalendarAlgorithm::Hebrew => fmt_impl(spec, prefs, pattern, Hebrew),
-        CalendarAlgorithm::Indian => fmt_impl(spec, prefs, pattern, Indian),
-        CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Civil)) => fmt_impl(
-            spec,
-            prefs,
-            pattern,
-            Hijri::<hijri::TabularAlgorithm>::new_tabular(
-                HijriTabularLeapYears::TypeII,
-                HijriTabularEpoch::Friday,
-            ),
-        ),
-        CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Tbla)) => fmt_impl(
-            spec,
-            prefs,
-            pattern,
-            Hijri::<hijri::TabularAlgorithm>::new_tabular(
-                HijriTabularLeapYears::TypeII,
-                HijriTabularEpoch::Thursday,
-            ),
-        ),
-        CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Umalqura)) => fmt_impl(
-            spec,
-            prefs,
-            pattern,
-            Hijri::<hijri::UmmAlQura>::new_umm_al_qura(),
-        ),
-        CalendarAlgorithm::Japanese => fmt_impl(spec, prefs, pattern, Japanese::new()),
-        CalendarAlgorithm::Persian => fmt_impl(spec, prefs, pattern, Persian),
-        CalendarAlgorithm::Roc => fmt_impl(spec, prefs, pattern, Roc),
-        CalendarAlgorithm::Iso8601 | CalendarAlgorithm::Hijri(_) => {
-            // unsupported
-            prefs.calendar_algorithm = None;
-            format_with_calendar(spec, prefs, pattern)
-        }
-        // unknown
-        _ => fmt_impl(spec, prefs, pattern, Gregorian),
-    }
+/// The names for a pattern in the calendar `C` (cached across calls).
+pub struct PatternNames<C: CldrCalendar> {
+    pub names: FixedCalendarDateTimeNames<C, CompositeFieldSet>,
+    pub pattern: DateTimePattern,
 }
 
 fn fmt_impl<C>(
     spec: &SpecifiedZonedDateTime,
     prefs: DateTimeFormatterPreferences,
-    pattern: &DateTimePattern,
+    pattern_str: &str,
     cal: C,
+    output: Output,
 ) -> Result<Vec<u8>, crate::Error>
 where
-    C: AsCalendar<Calendar = C> + CldrCalendar + icu_calendar::Calendar,
+    C: AsCalendar<Calendar = C> + CldrCalendar + icu_calendar::Calendar + 'static,
     icu_datetime::provider::Baked: DataProvider<<C as CldrCalendar>::YearNamesV1>,
     icu_datetime::provider::Baked: DataProvider<<C as CldrCalendar>::MonthNamesV1>,
+    for<'a> crate::data::Deserializing<'a>: DataProvider<<C as CldrCalendar>::YearNamesV1>,
+    for<'a> crate::data::Deserializing<'a>: DataProvider<<C as CldrCalendar>::MonthNamesV1>,
 {
-    let mut names = FixedCalendarDateTimeNames::<C, CompositeFieldSet>::try_new(prefs)?;
     let converted = ZonedDateTime {
         date: spec.value.date.to_calendar(cal),
         time: spec.value.time,
         zone: spec.value.zone,
     };
 
-    crate::write::try_to_vec(&names.include_for_pattern(pattern)?.format(&converted))
-        .map_err(crate::Error::FormattedPatternError)
+    with_names(prefs, pattern_str, |it: &PatternNames<C>| {
+        output.try_write(
+            &it.names
+                .with_pattern_unchecked(&it.pattern)
+                .format(&converted),
+        )
+    })
+}
+
+/// Calls `f` with the names for `pattern_str` in the calendar `C`, loading
+/// them if they're not cached.
+pub fn with_names<C, R>(
+    prefs: DateTimeFormatterPreferences,
+    pattern_str: &str,
+    f: impl FnOnce(&PatternNames<C>) -> Result<R, crate::Error>,
+) -> Result<R, crate::Error>
+where
+    C: CldrCalendar + 'static,
+    icu_datetime::provider::Baked: DataProvider<<C as CldrCalendar>::YearNamesV1>,
+    icu_datetime::provider::Baked: DataProvider<<C as CldrCalendar>::MonthNamesV1>,
+    for<'a> crate::data::Deserializing<'a>: DataProvider<<C as CldrCalendar>::YearNamesV1>,
+    for<'a> crate::data::Deserializing<'a>: DataProvider<<C as CldrCalendar>::MonthNamesV1>,
+{
+    crate::cache::with_pattern(&prefs, pattern_str, || load_names(&prefs, pattern_str), f)
+}
+
+fn load_names<C>(
+    prefs: &DateTimeFormatterPreferences,
+    pattern_str: &str,
+) -> Result<PatternNames<C>, crate::Error>
+where
+    C: CldrCalendar,
+    icu_datetime::provider::Baked: DataProvider<<C as CldrCalendar>::YearNamesV1>,
+    icu_datetime::provider::Baked: DataProvider<<C as CldrCalendar>::MonthNamesV1>,
+    for<'a> crate::data::Deserializing<'a>: DataProvider<<C as CldrCalendar>::YearNamesV1>,
+    for<'a> crate::data::Deserializing<'a>: DataProvider<<C as CldrCalendar>::MonthNamesV1>,
+{
+    let pattern = DateTimePattern::try_from_pattern_str(pattern_str)?;
+    let names = crate::data::create(
+        |provider| {
+            let provider = provider.as_deserializing();
+            let mut names = FixedCalendarDateTimeNames::<C, CompositeFieldSet>::try_new_unstable(
+                &provider,
+                prefs.clone(),
+            )?;
+            names.load_for_pattern(&provider, &pattern)?;
+            Ok(names)
+        },
+        || {
+            let mut names =
+                FixedCalendarDateTimeNames::<C, CompositeFieldSet>::try_new(prefs.clone())?;
+            names.include_for_pattern(&pattern)?;
+            Ok::<_, crate::Error>(names)
+        },
+    )?;
+    Ok(PatternNames { names, pattern })
+}
+
+/// Formats each of `values` with `pattern_str`.
+///
+/// This is for the patterns that the plugin uses internally. They are cached
+/// separately from the patterns of `experimental-pattern`.
+pub fn format_all<C>(
+    prefs: DateTimeFormatterPreferences,
+    pattern_str: &str,
+    values: &[ZonedDateTime<C, TimeZoneInfo<AtTime>>],
+) -> Result<Vec<String>, crate::Error>
+where
+    C: AsCalendar<Calendar = C> + CldrCalendar + icu_calendar::Calendar + 'static,
+    icu_datetime::provider::Baked: DataProvider<<C as CldrCalendar>::YearNamesV1>,
+    icu_datetime::provider::Baked: DataProvider<<C as CldrCalendar>::MonthNamesV1>,
+    for<'a> crate::data::Deserializing<'a>: DataProvider<<C as CldrCalendar>::YearNamesV1>,
+    for<'a> crate::data::Deserializing<'a>: DataProvider<<C as CldrCalendar>::MonthNamesV1>,
+{
+    let load = || load_names(&prefs, pattern_str);
+    crate::cache::with_internal_pattern(&prefs, pattern_str, load, |it: &PatternNames<C>| {
+        let formatter = it.names.with_pattern_unchecked(&it.pattern);
+        values
+            .iter()
+            .map(|value| {
+                formatter
+                    .format(value)
+                    .try_write_to_string()
+                    .map(Cow::into_owned)
+                    .map_err(|(e, _)| e.into())
+            })
+            .collect()
+    })
 }
@@ -0,0 +1,30 @@
+use fixed_decimal::Decimal;
+use icu_plurals::{PluralCategory, PluralRules};
+
+use crate::serde::PluralRuleTypeSerde;
+
+pub fn category(
+    number: &str,
+    locale: &str,
+    rule_type: PluralRuleTypeSerde,
+) -> Result<Vec<u8>, crate::Error> {
+    let prefs = crate::data::locale(locale)?.into();
+    // Parsing as a decimal keeps trailing zeros which are relevant for some
+    // languages (e.g. "1" is "one" in English but "1.0" is "other").
+    let number = Decimal::try_from_str(number)?;
+
+    let rules = match rule_type {
+        PluralRuleTypeSerde::Cardinal => PluralRules::try_new_cardinal(prefs)?,
+        PluralRuleTypeSerde::Ordinal => PluralRules::try_new_ordinal(prefs)?,
+    };
+
+    let category = match rules.category_for(&number) {
+        PluralCategory::Zero => "zero",
+        PluralCategory::One => "one",
+        PluralCategory::Two => "two",
+        PluralCategory::Few => "few",
+        PluralCategory::Many => "many",
+        PluralCategory::Other => "other",
+    };
+    Ok(category.as_bytes().to_vec())
+}
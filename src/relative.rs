@@ -0,0 +1,145 @@
+use fixed_decimal::Decimal;
+use icu_experimental::relativetime::{
+    RelativeTimeFormatter, RelativeTimeFormatterOptions, RelativeTimeFormatterPreferences,
+};
+use icu_provider::DataError;
+use serde::Deserialize;
+
+use crate::{
+    format::{SpecInput, SpecifiedZonedDateTime},
+    serde::{RelativeTimeOptionsSerde, RelativeTimeStyleSerde, RelativeTimeUnitSerde},
+};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum RelativeSpec {
+    Amount {
+        amount: i64,
+        unit: RelativeTimeUnitSerde,
+    },
+    Between {
+        from: SpecInput,
+        to: SpecInput,
+    },
+}
+
+pub fn format(
+    spec: RelativeSpec,
+    locale: &str,
+    opts: RelativeTimeOptionsSerde,
+) -> Result<Vec<u8>, crate::Error> {
+    let prefs = crate::data::locale(locale)?.into();
+
+    let (amount, unit) = match spec {
+        RelativeSpec::Amount { amount, unit } => (amount, unit),
+        RelativeSpec::Between { from, to } => difference(&from.try_into()?, &to.try_into()?),
+    };
+
+    let mut options = RelativeTimeFormatterOptions::default();
+    if let Some(numeric) = opts.numeric {
+        options.numeric = numeric.into();
+    }
+
+    let formatter = constructor(opts.style.unwrap_or_default(), unit)(prefs, options)?;
+
+    Ok(crate::write::to_vec(
+        formatter.format(Decimal::from(amount)),
+    ))
+}
+
+type Constructor = fn(
+    RelativeTimeFormatterPreferences,
+    RelativeTimeFormatterOptions,
+) -> Result<RelativeTimeFormatter, DataError>;
+
+fn constructor(style: RelativeTimeStyleSerde, unit: RelativeTimeUnitSerde) -> Constructor {
+    use RelativeTimeStyleSerde as S;
+    use RelativeTimeUnitSerde as U;
+
+    match (style, unit) {
+        (S::Long, U::Second) => RelativeTimeFormatter::try_new_long_second,
+        (S::Long, U::Minute) => RelativeTimeFormatter::try_new_long_minute,
+        (S::Long, U::Hour) => RelativeTimeFormatter::try_new_long_hour,
+        (S::Long, U::Day) => RelativeTimeFormatter::try_new_long_day,
+        (S::Long, U::Week) => RelativeTimeFormatter::try_new_long_week,
+        (S::Long, U::Month) => RelativeTimeFormatter::try_new_long_month,
+        (S::Long, U::Quarter) => RelativeTimeFormatter::try_new_long_quarter,
+        (S::Long, U::Year) => RelativeTimeFormatter::try_new_long_year,
+        (S::Short, U::Second) => RelativeTimeFormatter::try_new_short_second,
+        (S::Short, U::Minute) => RelativeTimeFormatter::try_new_short_minute,
+        (S::Short, U::Hour) => RelativeTimeFormatter::try_new_short_hour,
+        (S::Short, U::Day) => RelativeTimeFormatter::try_new_short_day,
+        (S::Short, U::Week) => RelativeTimeFormatter::try_new_short_week,
+        (S::Short, U::Month) => RelativeTimeFormatter::try_new_short_month,
+        (S::Short, U::Quarter) => RelativeTimeFormatter::try_new_short_quarter,
+        (S::Short, U::Year) => RelativeTimeFormatter::try_new_short_year,
+        (S::Narrow, U::Second) => RelativeTimeFormatter::try_new_narrow_second,
+        (S::Narrow, U::Minute) => RelativeTimeFormatter::try_new_narrow_minute,
+        (S::Narrow, U::Hour) => RelativeTimeFormatter::try_new_narrow_hour,
+        (S::Narrow, U::Day) => RelativeTimeFormatter::try_new_narrow_day,
+        (S::Narrow, U::Week) => RelativeTimeFormatter::try_new_narrow_week,
+        (S::Narrow, U::Month) => RelativeTimeFormatter::try_new_narrow_month,
+        (S::Narrow, U::Quarter) => RelativeTimeFormatter::try_new_narrow_quarter,
+        (S::Narrow, U::Year) => RelativeTimeFormatter::try_new_narrow_year,
+    }
+}
+
+/// Picks the largest unit in which `to` is at least one unit away from `from`.
+///
+/// If both sides have a time, seconds, minutes, and hours are considered as
+/// well. Otherwise, the difference is computed in calendar days.
+fn difference(
+    from: &SpecifiedZonedDateTime,
+    to: &SpecifiedZonedDateTime,
+) -> (i64, RelativeTimeUnitSerde) {
+    let days = to.value.date.to_rata_die() - from.value.date.to_rata_die();
+
+    if from.has_time && to.has_time {
+        let seconds = days * 86400 + seconds_of_day(to) - seconds_of_day(from);
+        match seconds.abs() {
+            0..60 => return (seconds, RelativeTimeUnitSerde::Second),
+            60..3600 => return (seconds / 60, RelativeTimeUnitSerde::Minute),
+            3600..86400 => return (seconds / 3600, RelativeTimeUnitSerde::Hour),
+            _ => (),
+        }
+    }
+
+    let mut months = (i64::from(to.value.date.extended_year())
+        - i64::from(from.value.date.extended_year()))
+        * 12
+        + i64::from(to.value.date.month().ordinal)
+        - i64::from(from.value.date.month().ordinal);
+    // only count a month once the day of the month has been reached
+    let (from_day, to_day) = (
+        from.value.date.day_of_month().0,
+        to.value.date.day_of_month().0,
+    );
+    if months > 0 && to_day < from_day {
+        months -= 1;
+    } else if months < 0 && to_day > from_day {
+        months += 1;
+    }
+
+    match (days.abs(), months.abs()) {
+        (0..7, _) => (days, RelativeTimeUnitSerde::Day),
+        (_, 0) => (days / 7, RelativeTimeUnitSerde::Week),
+        (_, 1..12) => (months, RelativeTimeUnitSerde::Month),
+        _ => (months / 12, RelativeTimeUnitSerde::Year),
+    }
+}
+
+/// Seconds since the start of the day in UTC (if there's an offset).
+fn seconds_of_day(spec: &SpecifiedZonedDateTime) -> i64 {
+    let time = spec.value.time;
+    let offset = spec
+        .value
+        .zone
+        .offset()
+        .map(|it| i64::from(it.to_seconds()))
+        .unwrap_or_default();
+
+    i64::from(time.hour.number()) * 3600
+        + i64::from(time.minute.number()) * 60
+        + i64::from(time.second.number())
+        - offset
+}
@@ -1,10 +1,23 @@
+use fixed_decimal::{SignDisplay, SignedRoundingMode, UnsignedRoundingMode};
+use icu_calendar::preferences::{CalendarAlgorithm, HijriCalendarAlgorithm};
+use icu_collator::{
+    options::{AlternateHandling, Strength},
+    preferences::CollationCaseFirst,
+};
 use icu_datetime::{
     fieldsets::builder::{DateFields, FieldSetBuilder, ZoneStyle},
     options::{Alignment, Length, SubsecondDigits, TimePrecision, YearStyle},
 };
+use icu_decimal::options::GroupingStrategy;
+use icu_experimental::{
+    displaynames::{LanguageDisplay, Style},
+    duration::options::BaseStyle,
+    relativetime::options::Numeric,
+};
+use icu_list::options::ListLength;
 
-// These types mirror the ones from  icu_datetime::fieldsets::builder with the
-// difference that they use kebab-case.
+// These types mirror the ones from  icu_datetime::fieldsets::builder (and other
+// ICU4X options) with the difference that they use kebab-case.
 
 #[derive(serde::Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -15,18 +28,40 @@ pub struct FieldSetBuilderSerde {
     pub zone_style: Option<ZoneStyleSerde>,
     pub alignment: Option<Alignment>,
     pub year_style: Option<YearStyleSerde>,
+    /// Not part of the builder - overrides the calendar of the locale.
+    pub calendar: Option<CalendarSerde>,
+    /// Not part of the builder - requires data for the exact locale.
+    #[serde(default)]
+    pub strict: bool,
+    /// Not part of the builder - returns the cache statistics of the call
+    /// (only used by `format_batch`).
+    #[serde(default)]
+    pub stats: bool,
+}
+
+/// The options of `format_pattern` (the other options of
+/// [`FieldSetBuilderSerde`] don't apply to patterns).
+#[derive(serde::Deserialize)]
+pub struct PatternOptionsSerde {
+    /// Overrides the calendar of the locale.
+    pub calendar: Option<CalendarSerde>,
+    /// Requires data for the exact locale.
+    #[serde(default)]
+    pub strict: bool,
 }
 
-impl From<FieldSetBuilderSerde> for FieldSetBuilder {
-    fn from(value: FieldSetBuilderSerde) -> Self {
+impl TryFrom<FieldSetBuilderSerde> for FieldSetBuilder {
+    type Error = crate::Error;
+
+    fn try_from(value: FieldSetBuilderSerde) -> Result<Self, Self::Error> {
         let mut builder = Self::new();
         builder.length = value.length;
         builder.date_fields = value.date_fields;
         builder.time_precision = value.time_precision.map(Into::into);
-        builder.zone_style = value.zone_style.map(Into::into);
+        builder.zone_style = value.zone_style.map(TryInto::try_into).transpose()?;
         builder.alignment = value.alignment;
         builder.year_style = value.year_style.map(Into::into);
-        builder
+        Ok(builder)
     }
 }
 
@@ -61,9 +96,11 @@ pub enum ZoneStyleSerde {
     ExemplarCity,
 }
 
-impl From<ZoneStyleSerde> for ZoneStyle {
-    fn from(value: ZoneStyleSerde) -> Self {
-        match value {
+impl TryFrom<ZoneStyleSerde> for ZoneStyle {
+    type Error = crate::Error;
+
+    fn try_from(value: ZoneStyleSerde) -> Result<Self, Self::Error> {
+        let style = match value {
             ZoneStyleSerde::SpecificLong => Self::SpecificLong,
             ZoneStyleSerde::SpecificShort => Self::SpecificShort,
             ZoneStyleSerde::LocalizedOffsetLong => Self::LocalizedOffsetLong,
@@ -72,7 +109,15 @@ impl From<ZoneStyleSerde> for ZoneStyle {
             ZoneStyleSerde::GenericShort => Self::GenericShort,
             ZoneStyleSerde::Location => Self::Location,
             ZoneStyleSerde::ExemplarCity => Self::ExemplarCity,
+        };
+        let is_offset = matches!(
+            value,
+            ZoneStyleSerde::LocalizedOffsetLong | ZoneStyleSerde::LocalizedOffsetShort
+        );
+        if !is_offset && !cfg!(feature = "zone-names") {
+            return Err(crate::Error::ExcludedZoneNames);
         }
+        Ok(style)
     }
 }
 
@@ -113,3 +158,367 @@ impl From<TimePrecisionSerde> for TimePrecision {
         }
     }
 }
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RelativeTimeOptionsSerde {
+    pub style: Option<RelativeTimeStyleSerde>,
+    pub numeric: Option<NumericSerde>,
+}
+
+#[derive(Copy, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RelativeTimeStyleSerde {
+    #[default]
+    Long,
+    Short,
+    Narrow,
+}
+
+#[derive(Copy, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RelativeTimeUnitSerde {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+#[derive(Copy, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NumericSerde {
+    Always,
+    Auto,
+}
+
+impl From<NumericSerde> for Numeric {
+    fn from(value: NumericSerde) -> Self {
+        match value {
+            NumericSerde::Always => Self::Always,
+            NumericSerde::Auto => Self::Auto,
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DurationOptionsSerde {
+    pub style: Option<DurationStyleSerde>,
+    /// The number of fraction digits of the smallest (sub)second unit that
+    /// is displayed numerically (0-9).
+    pub fractional_digits: Option<u8>,
+}
+
+#[derive(Copy, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DurationStyleSerde {
+    Long,
+    Short,
+    Narrow,
+    Digital,
+}
+
+impl From<DurationStyleSerde> for BaseStyle {
+    fn from(value: DurationStyleSerde) -> Self {
+        match value {
+            DurationStyleSerde::Long => Self::Long,
+            DurationStyleSerde::Short => Self::Short,
+            DurationStyleSerde::Narrow => Self::Narrow,
+            DurationStyleSerde::Digital => Self::Digital,
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DecimalOptionsSerde {
+    pub grouping: Option<GroupingStrategySerde>,
+    pub min_fraction_digits: Option<i16>,
+    pub max_fraction_digits: Option<i16>,
+    pub sign_display: Option<SignDisplaySerde>,
+    pub rounding_mode: Option<RoundingModeSerde>,
+}
+
+#[derive(Copy, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GroupingStrategySerde {
+    Auto,
+    Never,
+    Always,
+    Min2,
+}
+
+impl From<GroupingStrategySerde> for GroupingStrategy {
+    fn from(value: GroupingStrategySerde) -> Self {
+        match value {
+            GroupingStrategySerde::Auto => Self::Auto,
+            GroupingStrategySerde::Never => Self::Never,
+            GroupingStrategySerde::Always => Self::Always,
+            GroupingStrategySerde::Min2 => Self::Min2,
+        }
+    }
+}
+
+#[derive(Copy, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignDisplaySerde {
+    Auto,
+    Never,
+    Always,
+    ExceptZero,
+    Negative,
+}
+
+impl From<SignDisplaySerde> for SignDisplay {
+    fn from(value: SignDisplaySerde) -> Self {
+        match value {
+            SignDisplaySerde::Auto => Self::Auto,
+            SignDisplaySerde::Never => Self::Never,
+            SignDisplaySerde::Always => Self::Always,
+            SignDisplaySerde::ExceptZero => Self::ExceptZero,
+            SignDisplaySerde::Negative => Self::Negative,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RoundingModeSerde {
+    Ceil,
+    Floor,
+    Expand,
+    Trunc,
+    HalfCeil,
+    HalfFloor,
+    #[default]
+    HalfExpand,
+    HalfTrunc,
+    HalfEven,
+}
+
+impl From<RoundingModeSerde> for SignedRoundingMode {
+    fn from(value: RoundingModeSerde) -> Self {
+        match value {
+            RoundingModeSerde::Ceil => Self::Ceil,
+            RoundingModeSerde::Floor => Self::Floor,
+            RoundingModeSerde::Expand => Self::Unsigned(UnsignedRoundingMode::Expand),
+            RoundingModeSerde::Trunc => Self::Unsigned(UnsignedRoundingMode::Trunc),
+            RoundingModeSerde::HalfCeil => Self::HalfCeil,
+            RoundingModeSerde::HalfFloor => Self::HalfFloor,
+            RoundingModeSerde::HalfExpand => Self::Unsigned(UnsignedRoundingMode::HalfExpand),
+            RoundingModeSerde::HalfTrunc => Self::Unsigned(UnsignedRoundingMode::HalfTrunc),
+            RoundingModeSerde::HalfEven => Self::Unsigned(UnsignedRoundingMode::HalfEven),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PluralRuleTypeSerde {
+    #[default]
+    Cardinal,
+    Ordinal,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListOptionsSerde {
+    pub kind: Option<ListKindSerde>,
+    pub width: Option<ListLengthSerde>,
+}
+
+#[derive(Copy, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ListKindSerde {
+    #[default]
+    And,
+    Or,
+    Unit,
+}
+
+#[derive(Copy, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ListLengthSerde {
+    Wide,
+    Short,
+    Narrow,
+}
+
+impl From<ListLengthSerde> for ListLength {
+    fn from(value: ListLengthSerde) -> Self {
+        match value {
+            ListLengthSerde::Wide => Self::Wide,
+            ListLengthSerde::Short => Self::Short,
+            ListLengthSerde::Narrow => Self::Narrow,
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CollatorOptionsSerde {
+    pub strength: Option<StrengthSerde>,
+    pub case_first: Option<CaseFirstSerde>,
+    pub numeric: Option<bool>,
+    pub alternate_handling: Option<AlternateHandlingSerde>,
+    /// Return the indices of the sorted items instead of the items.
+    pub indices: Option<bool>,
+}
+
+#[derive(Copy, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StrengthSerde {
+    Primary,
+    Secondary,
+    Tertiary,
+    Quaternary,
+    Identical,
+}
+
+impl From<StrengthSerde> for Strength {
+    fn from(value: StrengthSerde) -> Self {
+        match value {
+            StrengthSerde::Primary => Self::Primary,
+            StrengthSerde::Secondary => Self::Secondary,
+            StrengthSerde::Tertiary => Self::Tertiary,
+            StrengthSerde::Quaternary => Self::Quaternary,
+            StrengthSerde::Identical => Self::Identical,
+        }
+    }
+}
+
+#[derive(Copy, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CaseFirstSerde {
+    Upper,
+    Lower,
+    Off,
+}
+
+impl From<CaseFirstSerde> for CollationCaseFirst {
+    fn from(value: CaseFirstSerde) -> Self {
+        match value {
+            CaseFirstSerde::Upper => Self::Upper,
+            CaseFirstSerde::Lower => Self::Lower,
+            CaseFirstSerde::Off => Self::False,
+        }
+    }
+}
+
+#[derive(Copy, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AlternateHandlingSerde {
+    NonIgnorable,
+    Shifted,
+}
+
+impl From<AlternateHandlingSerde> for AlternateHandling {
+    fn from(value: AlternateHandlingSerde) -> Self {
+        match value {
+            AlternateHandlingSerde::NonIgnorable => Self::NonIgnorable,
+            AlternateHandlingSerde::Shifted => Self::Shifted,
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DisplayNamesOptionsSerde {
+    pub style: Option<DisplayNamesStyleSerde>,
+    pub language_display: Option<LanguageDisplaySerde>,
+}
+
+#[derive(Copy, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DisplayNamesStyleSerde {
+    Long,
+    Short,
+    Narrow,
+    Menu,
+}
+
+impl From<DisplayNamesStyleSerde> for Style {
+    fn from(value: DisplayNamesStyleSerde) -> Self {
+        match value {
+            DisplayNamesStyleSerde::Long => Self::Long,
+            DisplayNamesStyleSerde::Short => Self::Short,
+            DisplayNamesStyleSerde::Narrow => Self::Narrow,
+            DisplayNamesStyleSerde::Menu => Self::Menu,
+        }
+    }
+}
+
+#[derive(Copy, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LanguageDisplaySerde {
+    Standard,
+    Dialect,
+}
+
+impl From<LanguageDisplaySerde> for LanguageDisplay {
+    fn from(value: LanguageDisplaySerde) -> Self {
+        match value {
+            LanguageDisplaySerde::Standard => Self::Standard,
+            LanguageDisplaySerde::Dialect => Self::Dialect,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DisambiguationSerde {
+    #[default]
+    Compatible,
+    Earlier,
+    Later,
+    Reject,
+}
+
+/// The calendars supported by ICU4X with their BCP-47 identifiers.
+#[derive(Copy, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CalendarSerde {
+    Buddhist,
+    Chinese,
+    Coptic,
+    Dangi,
+    Ethioaa,
+    Ethiopic,
+    Gregory,
+    Hebrew,
+    Indian,
+    IslamicCivil,
+    IslamicTbla,
+    IslamicUmalqura,
+    Iso8601,
+    Japanese,
+    Persian,
+    Roc,
+}
+
+impl From<CalendarSerde> for CalendarAlgorithm {
+    fn from(value: CalendarSerde) -> Self {
+        match value {
+            CalendarSerde::Buddhist => Self::Buddhist,
+            CalendarSerde::Chinese => Self::Chinese,
+            CalendarSerde::Coptic => Self::Coptic,
+            CalendarSerde::Dangi => Self::Dangi,
+            CalendarSerde::Ethioaa => Self::Ethioaa,
+            CalendarSerde::Ethiopic => Self::Ethiopic,
+            CalendarSerde::Gregory => Self::Gregory,
+            CalendarSerde::Hebrew => Self::Hebrew,
+            CalendarSerde::Indian => Self::Indian,
+            CalendarSerde::IslamicCivil => Self::Hijri(Some(HijriCalendarAlgorithm::Civil)),
+            CalendarSerde::IslamicTbla => Self::Hijri(Some(HijriCalendarAlgorithm::Tbla)),
+            CalendarSerde::IslamicUmalqura => Self::Hijri(Some(HijriCalendarAlgorithm::Umalqura)),
+            CalendarSerde::Iso8601 => Self::Iso8601,
+            CalendarSerde::Japanese => Self::Japanese,
+            CalendarSerde::Persian => Self::Persian,
+            CalendarSerde::Roc => Self::Roc,
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! The localized names of months, weekdays, eras, and day periods.
+
+use icu_calendar::{preferences::CalendarAlgorithm, AsCalendar, Date, Iso};
+use icu_datetime::{scaffold::CldrCalendar, DateTimeFormatterPreferences};
+use icu_provider::DataProvider;
+use icu_time::{zone::models::AtTime, Time, TimeZoneInfo, ZonedDateTime};
+use serde::Serialize;
+
+use crate::{calendar::with_calendar, format::SpecifiedZonedDateTime};
+
+/// Dates with different eras in all calendars (e.g. BCE and CE in the
+/// Gregorian calendar and the modern eras in the Japanese calendar).
+const ERA_SAMPLES: [i32; 7] = [-1000, 1000, 1873, 1913, 1927, 1990, 2020];
+
+type Value<C> = ZonedDateTime<C, TimeZoneInfo<AtTime>>;
+
+#[derive(Serialize)]
+struct Widths {
+    wide: Vec<String>,
+    abbreviated: Vec<String>,
+    narrow: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct WeekdayWidths {
+    wide: Vec<String>,
+    abbreviated: Vec<String>,
+    short: Vec<String>,
+    narrow: Vec<String>,
+}
+
+/// The names used in dates (`format`) and on their own (`standalone`, e.g. in
+/// headings).
+#[derive(Serialize)]
+struct Contexts<T> {
+    format: T,
+    standalone: T,
+}
+
+#[derive(Serialize)]
+struct Months {
+    year: i32,
+    codes: Vec<String>,
+    #[serde(flatten)]
+    names: Contexts<Widths>,
+}
+
+#[derive(Serialize)]
+struct Era {
+    code: String,
+    wide: String,
+    abbreviated: String,
+    narrow: String,
+}
+
+#[derive(Serialize)]
+struct DayPeriods {
+    am: String,
+    pm: String,
+    noon: String,
+    midnight: String,
+}
+
+#[derive(Serialize)]
+struct DayPeriodWidths {
+    wide: DayPeriods,
+    abbreviated: DayPeriods,
+    narrow: DayPeriods,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Symbols {
+    months: Months,
+    weekdays: Contexts<WeekdayWidths>,
+    eras: Vec<Era>,
+    day_periods: DayPeriodWidths,
+}
+
+/// Serializes the names in `calendar` (or the calendar of `locale`). The month
+/// names are the ones of `year` (in the calendar), because some calendars have
+/// leap months.
+pub fn names(
+    locale: &str,
+    calendar: Option<CalendarAlgorithm>,
+    year: Option<i32>,
+) -> Result<Vec<u8>, crate::Error> {
+    let locale = crate::data::locale(locale)?;
+    let mut prefs: DateTimeFormatterPreferences = (&locale).into();
+    if calendar.is_some() {
+        prefs.calendar_algorithm = calendar;
+    }
+    let algorithm = crate::calendar::resolve(&prefs)?;
+    prefs.calendar_algorithm = Some(algorithm);
+
+    let symbols = with_calendar!(algorithm, |cal| symbols_in(prefs, year, cal))?;
+
+    let mut w = vec![];
+    ciborium::into_writer(&symbols, &mut w)?;
+    Ok(w)
+}
+
+fn symbols_in<C>(
+    prefs: DateTimeFormatterPreferences,
+    year: Option<i32>,
+    cal: C,
+) -> Result<Symbols, crate::Error>
+where
+    C: AsCalendar<Calendar = C> + CldrCalendar + icu_calendar::Calendar + Clone + 'static,
+    icu_datetime::provider::Baked: DataProvider<<C as CldrCalendar>::YearNamesV1>,
+    icu_datetime::provider::Baked: DataProvider<<C as CldrCalendar>::MonthNamesV1>,
+    for<'a> crate::data::Deserializing<'a>: DataProvider<<C as CldrCalendar>::YearNamesV1>,
+    for<'a> crate::data::Deserializing<'a>: DataProvider<<C as CldrCalendar>::MonthNamesV1>,
+{
+    let at = |date: Date<Iso>, hour: u8| -> Result<Value<C>, crate::Error> {
+        let value = SpecifiedZonedDateTime::from_date(date).value;
+        Ok(ZonedDateTime {
+            date: date.to_calendar(cal.clone()),
+            time: Time::try_new(hour, 0, 0, 0).map_err(crate::Error::DateRange)?,
+            zone: value.zone,
+        })
+    };
+    let format_all =
+        |pattern: &str, values: &[_]| crate::pattern::format_all(prefs.clone(), pattern, values);
+
+    // 2024 is a leap year in the Hebrew and Chinese calendars
+    let reference = Date::try_new_iso(2024, 1, 1).map_err(crate::Error::DateRange)?;
+    let year = year.unwrap_or_else(|| reference.to_calendar(cal.clone()).extended_year());
+    let first = crate::format::date_in(cal.clone(), None, year, None, Some(1), 1)?;
+    let months = (1..=first.months_in_year())
+        .map(|month| {
+            let date = crate::format::date_in(cal.clone(), None, year, None, Some(month), 1)?;
+            at(date.to_calendar(Iso), 0)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let widths = |[wide, abbreviated, narrow]: [&str; 3]| {
+        Ok::<_, crate::Error>(Widths {
+            wide: format_all(wide, &months)?,
+            abbreviated: format_all(abbreviated, &months)?,
+            narrow: format_all(narrow, &months)?,
+        })
+    };
+    let months = Months {
+        year,
+        codes: months
+            .iter()
+            .map(|it| it.date.month().standard_code.0.to_string())
+            .collect(),
+        names: Contexts {
+            format: widths(["MMMM", "MMM", "MMMMM"])?,
+            standalone: widths(["LLLL", "LLL", "LLLLL"])?,
+        },
+    };
+
+    // Monday to Sunday
+    let weekdays = (0..7)
+        .map(|i| at(Date::from_rata_die(reference.to_rata_die() + i, Iso), 0))
+        .collect::<Result<Vec<_>, _>>()?;
+    let widths = |[wide, abbreviated, short, narrow]: [&str; 4]| {
+        Ok::<_, crate::Error>(WeekdayWidths {
+            wide: format_all(wide, &weekdays)?,
+            abbreviated: format_all(abbreviated, &weekdays)?,
+            short: format_all(short, &weekdays)?,
+            narrow: format_all(narrow, &weekdays)?,
+        })
+    };
+    let weekdays = Contexts {
+        format: widths(["EEEE", "EEE", "EEEEEE", "EEEEE"])?,
+        standalone: widths(["cccc", "ccc", "cccccc", "ccccc"])?,
+    };
+
+    let (mut era_codes, mut era_values) = (Vec::new(), Vec::new());
+    for year in ERA_SAMPLES {
+        let value = at(
+            Date::try_new_iso(year, 1, 1).map_err(crate::Error::DateRange)?,
+            0,
+        )?;
+        let Some(era) = value.date.year().era() else {
+            // the calendar doesn't have eras (e.g. the Chinese calendar)
+            break;
+        };
+        if !era_codes.contains(&era.era) {
+            era_codes.push(era.era);
+            era_values.push(value);
+        }
+    }
+    let eras = if era_values.is_empty() {
+        Vec::new()
+    } else {
+        era_codes
+            .iter()
+            .zip(format_all("GGGG", &era_values)?)
+            .zip(format_all("G", &era_values)?)
+            .zip(format_all("GGGGG", &era_values)?)
+            .map(|(((code, wide), abbreviated), narrow)| Era {
+                code: code.to_string(),
+                wide,
+                abbreviated,
+                narrow,
+            })
+            .collect()
+    };
+
+    // 9:00, 15:00, noon, and midnight
+    let times = [9, 15, 12, 0]
+        .into_iter()
+        .map(|hour| at(reference, hour))
+        .collect::<Result<Vec<_>, _>>()?;
+    let day_periods = |am_pm: &str, noon_midnight: &str| {
+        let [am, pm] = <[String; 2]>::try_from(format_all(am_pm, &times[..2])?)
+            .expect("two times were formatted");
+        let [noon, midnight] = <[String; 2]>::try_from(format_all(noon_midnight, &times[2..])?)
+            .expect("two times were formatted");
+        Ok::<_, crate::Error>(DayPeriods {
+            am,
+            pm,
+            noon,
+            midnight,
+        })
+    };
+
+    Ok(Symbols {
+        months,
+        weekdays,
+        eras,
+        day_periods: DayPeriodWidths {
+            wide: day_periods("aaaa", "bbbb")?,
+            abbreviated: day_periods("a", "b")?,
+            narrow: day_periods("aaaaa", "bbbbb")?,
+        },
+    })
+}
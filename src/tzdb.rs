@@ -0,0 +1,170 @@
+//! Time zone rules from the bundled TZDB.
+//!
+//! ICU4X only knows the names and the variant offsets of time zones, but not
+//! when a zone switches between them. This module uses jiff's copy of the TZDB
+//! to fill that gap.
+
+use icu_calendar::{Date, Iso};
+use icu_time::{
+    zone::{iana::IanaParserExtendedBorrowed, UtcOffset, VariantOffsets},
+    DateTime, Time, TimeZone,
+};
+use jiff::tz::{AmbiguousOffset, TimeZoneTransition};
+
+use crate::{serde::DisambiguationSerde, InvalidVariantOffsets};
+
+/// Resolves the UTC offset that `zone` has at the local (wall-clock) time
+/// `local`. Returns the local time (which only differs from `local` in a gap)
+/// and the offset.
+///
+/// If `local` doesn't exist (gap) or exists twice (overlap) in the zone,
+/// `disambiguation` selects the instant like the corresponding option in
+/// Temporal. In a gap, the local time is moved by the length of the gap
+/// (e.g. 02:30 becomes 03:30 with `compatible` and `later`, and 01:30 with
+/// `earlier`, if the clocks are moved from 02:00 to 03:00).
+pub fn resolve_offset(
+    zone: TimeZone,
+    iana: Option<&str>,
+    local: DateTime<Iso>,
+    disambiguation: DisambiguationSerde,
+) -> Result<(DateTime<Iso>, UtcOffset), crate::Error> {
+    use DisambiguationSerde as D;
+
+    let tz = lookup(zone, iana)?;
+    let ambiguous = tz.to_ambiguous_timestamp(to_civil(local)?);
+    let timestamp = match (ambiguous.offset(), disambiguation) {
+        (AmbiguousOffset::Gap { .. }, D::Reject) => return Err(crate::Error::NonexistentTime),
+        (AmbiguousOffset::Fold { .. }, D::Reject) => return Err(crate::Error::AmbiguousTime),
+        (_, D::Compatible | D::Reject) => ambiguous.compatible(),
+        (_, D::Earlier) => ambiguous.earlier(),
+        (_, D::Later) => ambiguous.later(),
+    }
+    .map_err(crate::Error::Tzdb)?;
+
+    Ok((
+        from_civil(tz.to_datetime(timestamp))?,
+        to_utc_offset(tz.to_offset(timestamp))?,
+    ))
+}
+
+/// Checks that `offset` is either the standard or the daylight offset of
+/// `zone` around the local time `local`.
+pub fn check_offset(
+    zone: TimeZone,
+    iana: Option<&str>,
+    local: DateTime<Iso>,
+    offset: UtcOffset,
+) -> Result<(), crate::Error> {
+    let offsets = variant_offsets(zone, iana, local)?;
+    if offsets.standard == offset || offsets.daylight == Some(offset) {
+        Ok(())
+    } else {
+        Err(crate::Error::OffsetMismatch(InvalidVariantOffsets(offsets)))
+    }
+}
+
+/// Gets the standard and daylight offsets that `zone` uses around the local
+/// time `local`.
+///
+/// The variant that isn't in effect at `local` is taken from the closest
+/// transition within a year.
+fn variant_offsets(
+    zone: TimeZone,
+    iana: Option<&str>,
+    local: DateTime<Iso>,
+) -> Result<VariantOffsets, crate::Error> {
+    const MAX_DISTANCE: i64 = 366 * 24 * 60 * 60;
+
+    let tz = lookup(zone, iana)?;
+    let timestamp = tz
+        .to_ambiguous_timestamp(to_civil(local)?)
+        .compatible()
+        .map_err(crate::Error::Tzdb)?;
+    let info = tz.to_offset_info(timestamp);
+    let is_dst = info.dst().is_dst();
+
+    let within_distance = |t: &TimeZoneTransition| {
+        (t.timestamp().as_second() - timestamp.as_second()).abs() <= MAX_DISTANCE
+    };
+    let other = tz
+        .preceding(timestamp)
+        .take_while(within_distance)
+        .chain(tz.following(timestamp).take_while(within_distance))
+        .filter(|t| t.dst().is_dst() != is_dst)
+        .min_by_key(|t| (t.timestamp().as_second() - timestamp.as_second()).abs())
+        .map(|t| to_utc_offset(t.offset()))
+        .transpose()?;
+
+    let current = to_utc_offset(info.offset())?;
+    let (standard, daylight) = match (is_dst, other) {
+        (false, other) => (current, other),
+        (true, Some(standard)) => (standard, Some(current)),
+        // permanent daylight saving time
+        (true, None) => (current, None),
+    };
+    let mut offsets = VariantOffsets::from_standard(standard);
+    offsets.daylight = daylight;
+
+    Ok(offsets)
+}
+
+/// Converts the UTC time `utc` to the local time in the zone `iana`. Returns
+/// the local time and its offset in seconds.
+pub fn utc_to_local(
+    iana: &str,
+    utc: jiff::civil::DateTime,
+) -> Result<(jiff::civil::DateTime, i32), crate::Error> {
+    let tz = jiff::tz::TimeZone::get(iana).map_err(crate::Error::Tzdb)?;
+    let zoned = utc
+        .to_zoned(jiff::tz::TimeZone::UTC)
+        .map_err(crate::Error::Tzdb)?
+        .with_time_zone(tz);
+
+    Ok((zoned.datetime(), zoned.offset().seconds()))
+}
+
+fn to_utc_offset(offset: jiff::tz::Offset) -> Result<UtcOffset, crate::Error> {
+    UtcOffset::try_from_seconds(offset.seconds()).map_err(|_| crate::Error::InvalidOffset)
+}
+
+fn lookup(zone: TimeZone, iana: Option<&str>) -> Result<jiff::tz::TimeZone, crate::Error> {
+    let name = match iana {
+        Some(name) => name,
+        None => IanaParserExtendedBorrowed::new()
+            .iter_all()
+            .find(|it| it.time_zone == zone)
+            .map(|it| it.canonical)
+            .ok_or(crate::Error::UnknownIana)?,
+    };
+
+    jiff::tz::TimeZone::get(name).map_err(crate::Error::Tzdb)
+}
+
+fn to_civil(value: DateTime<Iso>) -> Result<jiff::civil::DateTime, crate::Error> {
+    let DateTime { date, time } = value;
+    jiff::civil::DateTime::new(
+        // out of range years are rejected by jiff
+        date.extended_year().try_into().unwrap_or(i16::MAX),
+        date.month().ordinal as i8,
+        date.day_of_month().0 as i8,
+        time.hour.number() as i8,
+        time.minute.number() as i8,
+        time.second.number() as i8,
+        time.subsecond.number() as i32,
+    )
+    .map_err(crate::Error::Tzdb)
+}
+
+fn from_civil(value: jiff::civil::DateTime) -> Result<DateTime<Iso>, crate::Error> {
+    Ok(DateTime {
+        date: Date::try_new_iso(value.year().into(), value.month() as u8, value.day() as u8)
+            .map_err(crate::Error::DateRange)?,
+        time: Time::try_new(
+            value.hour() as u8,
+            value.minute() as u8,
+            value.second() as u8,
+            value.subsec_nanosecond() as u32,
+        )
+        .map_err(crate::Error::DateRange)?,
+    })
+}
@@ -0,0 +1,189 @@
+//! Week data (first day and weekend) and week numbers.
+//!
+//! ICU4X doesn't provide the minimal number of days in the first week of a
+//! year, nor week numbers for other rules than ISO 8601, so they're computed
+//! here.
+
+use icu_calendar::{types::Weekday, week::WeekInformation, Date, Iso};
+use icu_locale_core::Locale;
+use serde::Serialize;
+
+use crate::format::{SpecInput, SpecifiedZonedDateTime};
+
+/// The regions where the first week of a year must have at least four days.
+/// Everywhere else, the week with January 1st is the first week.
+///
+/// See weekData in <https://github.com/unicode-org/cldr/blob/main/common/supplemental/supplementalData.xml>
+const MIN_DAYS_4: &[&str] = &[
+    "AD", "AN", "AT", "AX", "BE", "BG", "CH", "CZ", "DE", "DK", "EE", "ES", "FI", "FJ", "FO", "FR",
+    "GB", "GF", "GG", "GI", "GP", "GR", "HU", "IE", "IM", "IS", "IT", "JE", "LI", "LT", "LU", "MC",
+    "MQ", "NL", "NO", "PL", "PT", "RE", "RU", "SE", "SJ", "SK", "SM", "VA",
+];
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+];
+
+/// Rules for numbering weeks.
+#[derive(Clone, Copy)]
+pub struct WeekRules {
+    pub first_day: Weekday,
+    /// The minimal number of days in the first week of a year (or month).
+    pub min_days: u8,
+}
+
+impl WeekRules {
+    const ISO: Self = Self {
+        first_day: Weekday::Monday,
+        min_days: 4,
+    };
+
+    /// The rules that are used in `locale`.
+    pub fn for_locale(locale: &Locale, week: &WeekInformation) -> Self {
+        Self {
+            first_day: week.first_weekday,
+            min_days: match crate::locale::region(locale) {
+                Some(region) if MIN_DAYS_4.contains(&region.as_str()) => 4,
+                _ => 1,
+            },
+        }
+    }
+
+    /// The position of `day` in a week with these rules (0-6).
+    pub fn position(self, day: Weekday) -> i32 {
+        (day as i32 - self.first_day as i32).rem_euclid(7)
+    }
+
+    pub fn week_of_year(self, date: Date<Iso>) -> (i32, u8) {
+        let year = date.extended_year();
+        let day_of_year = i32::from(date.day_of_year().0);
+        let week_start = day_of_year - self.position(date.day_of_week());
+
+        // The week belongs to the year that this day is in (e.g. Thursday
+        // for ISO weeks).
+        let mut reference = week_start + 7 - i32::from(self.min_days);
+        let mut week_year = year;
+        if reference < 1 {
+            week_year -= 1;
+            reference += days_in_year(week_year);
+        } else if reference > days_in_year(year) {
+            week_year += 1;
+            reference -= days_in_year(year);
+        }
+
+        (week_year, ((reference - 1) / 7 + 1) as u8)
+    }
+
+    /// The week of the month (like `W` in patterns). Days before the first
+    /// week with at least `min_days` days in the month are in week 0.
+    fn week_of_month(self, date: Date<Iso>) -> u8 {
+        let day = i32::from(date.day_of_month().0);
+        let month_start = (self.position(date.day_of_week()) - (day - 1)).rem_euclid(7);
+
+        let mut week = (day - 1 + month_start) / 7;
+        if 7 - month_start >= i32::from(self.min_days) {
+            week += 1;
+        }
+        week as u8
+    }
+
+    fn week_of(self, date: Date<Iso>) -> WeekOf {
+        let (week_year, week_of_year) = self.week_of_year(date);
+        WeekOf {
+            week_of_year,
+            week_year,
+            week_of_month: self.week_of_month(date),
+        }
+    }
+}
+
+fn days_in_year(year: i32) -> i32 {
+    if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) {
+        366
+    } else {
+        365
+    }
+}
+
+/// The BCP-47 code of the weekday (like in `-u-fw`).
+pub fn code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Monday => "mon",
+        Weekday::Tuesday => "tue",
+        Weekday::Wednesday => "wed",
+        Weekday::Thursday => "thu",
+        Weekday::Friday => "fri",
+        Weekday::Saturday => "sat",
+        Weekday::Sunday => "sun",
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct WeekOf {
+    week_of_year: u8,
+    /// The year that the week belongs to (can be different from the year of
+    /// the date at the start and end of a year).
+    week_year: i32,
+    week_of_month: u8,
+}
+
+#[derive(Serialize)]
+struct Weeks {
+    iso: WeekOf,
+    locale: WeekOf,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct WeekInfo {
+    first_day: &'static str,
+    /// The weekend days (from Monday to Sunday).
+    weekend: Vec<&'static str>,
+    min_days: u8,
+    week: Option<Weeks>,
+}
+
+/// Serializes the week data of `locale` and (if `spec` is specified) the week
+/// numbers of its date.
+pub fn info(locale: &str, spec: Option<SpecInput>) -> Result<Vec<u8>, crate::Error> {
+    let locale = crate::data::locale(locale)?;
+    let week = WeekInformation::try_new((&locale).into())?;
+
+    let rules = WeekRules::for_locale(&locale, &week);
+
+    let weeks = match spec {
+        Some(spec) => {
+            let spec: SpecifiedZonedDateTime = spec.try_into()?;
+            if !spec.has_date {
+                return Err(crate::Error::MissingValues);
+            }
+            Some(Weeks {
+                iso: WeekRules::ISO.week_of(spec.value.date),
+                locale: rules.week_of(spec.value.date),
+            })
+        }
+        None => None,
+    };
+
+    let info = WeekInfo {
+        first_day: code(rules.first_day),
+        weekend: WEEKDAYS
+            .into_iter()
+            .filter(|day| week.weekend.contains(*day))
+            .map(code)
+            .collect(),
+        min_days: rules.min_days,
+        week: weeks,
+    };
+
+    let mut w = vec![];
+    ciborium::into_writer(&info, &mut w)?;
+    Ok(w)
+}
@@ -1,3 +1,8 @@
+use std::fmt;
+
+use serde::Serialize;
+use writeable::{Part, PartsWrite, TryWriteable, Writeable};
+
 pub fn to_vec(it: impl writeable::Writeable) -> Vec<u8> {
     it.write_to_string().into_owned().into_bytes()
 }
@@ -10,3 +15,143 @@ where
         .map(|it| it.into_owned().into_bytes())
         .map_err(|(e, _)| e)
 }
+
+/// Whether to write the formatted string or its parts.
+#[derive(Clone, Copy)]
+pub enum Output {
+    Text,
+    /// A CBOR list of `{text, part}` segments (see [`PartsCollector`]).
+    Parts,
+}
+
+impl Output {
+    pub fn write(self, it: impl Writeable) -> Result<Vec<u8>, crate::Error> {
+        match self {
+            Self::Text => Ok(to_vec(it)),
+            Self::Parts => {
+                let mut collector = PartsCollector::default();
+                it.write_to_parts(&mut collector)?;
+                collector.into_vec()
+            }
+        }
+    }
+
+    pub fn try_write<W>(self, it: &W) -> Result<Vec<u8>, crate::Error>
+    where
+        W: TryWriteable,
+        crate::Error: From<W::Error>,
+    {
+        match self {
+            Self::Text => Ok(try_to_vec(it)?),
+            Self::Parts => {
+                let mut collector = PartsCollector::default();
+                it.try_write_to_parts(&mut collector)??;
+                collector.into_vec()
+            }
+        }
+    }
+}
+
+pub struct Segment {
+    pub text: String,
+    /// The name of the part in ICU4X (e.g. `dayPeriod`).
+    pub part: &'static str,
+}
+
+#[derive(Serialize)]
+struct SerializedSegment {
+    text: String,
+    part: String,
+}
+
+/// Splits the output into segments by the outermost part that they're in.
+///
+/// Nested parts (e.g. the digits of a year) are attributed to the outer part.
+/// Text outside of any part is a `literal`.
+#[derive(Default)]
+pub struct PartsCollector {
+    segments: Vec<Segment>,
+    depth: usize,
+}
+
+impl PartsCollector {
+    pub const LITERAL: &'static str = "literal";
+
+    /// Writes `it` and collects its (non-empty) segments.
+    pub fn collect(it: impl Writeable) -> Result<Vec<Segment>, crate::Error> {
+        let mut collector = Self::default();
+        it.write_to_parts(&mut collector)?;
+        Ok(collector.into_segments())
+    }
+
+    fn into_segments(self) -> Vec<Segment> {
+        self.segments
+            .into_iter()
+            .filter(|it| !it.text.is_empty())
+            .collect()
+    }
+
+    fn into_vec(self) -> Result<Vec<u8>, crate::Error> {
+        let segments = self
+            .into_segments()
+            .into_iter()
+            .map(|it| SerializedSegment {
+                part: kebab_case(it.part),
+                text: it.text,
+            })
+            .collect::<Vec<_>>();
+
+        let mut w = vec![];
+        ciborium::into_writer(&segments, &mut w)?;
+        Ok(w)
+    }
+}
+
+impl fmt::Write for PartsCollector {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self.segments.last_mut() {
+            Some(last) if self.depth > 0 || last.part == Self::LITERAL => last.text.push_str(s),
+            _ => self.segments.push(Segment {
+                text: s.to_owned(),
+                part: Self::LITERAL,
+            }),
+        }
+        Ok(())
+    }
+}
+
+impl PartsWrite for PartsCollector {
+    type SubPartsWrite = Self;
+
+    fn with_part(
+        &mut self,
+        part: Part,
+        mut f: impl FnMut(&mut Self::SubPartsWrite) -> fmt::Result,
+    ) -> fmt::Result {
+        if self.depth == 0 {
+            self.segments.push(Segment {
+                text: String::new(),
+                part: part.value,
+            });
+        }
+        self.depth += 1;
+        let res = f(self);
+        self.depth -= 1;
+        res
+    }
+}
+
+/// Converts the part names from ICU4X (e.g. `dayPeriod`) to kebab-case
+/// (`day-period`).
+fn kebab_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 2);
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('-');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
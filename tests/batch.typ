@@ -0,0 +1,38 @@
+#import "../typst/api.typ" as icu
+
+#{
+  let dates = range(1, 4).map(d => (year: 2025, month: 1, day: d))
+  assert.eq(icu.fmt-batch(dates), ("Jan 1, 2025", "Jan 2, 2025", "Jan 3, 2025"))
+  assert.eq(icu.fmt-batch(dates, date-fields: "E", length: "long"), ("Wednesday", "Thursday", "Friday"))
+  assert.eq(icu.fmt-batch(dates, locale: "de"), dates.map(d => icu.fmt(d, locale: "de")))
+  assert.eq(icu.fmt-batch(()), ())
+
+  // mixed inputs
+  assert.eq(
+    icu.fmt-batch(
+      (datetime(year: 2025, month: 1, day: 1), "2025-01-02", "2025-01-03[u-ca=hebrew]"),
+      date-fields: "YMD",
+    ),
+    ("Jan 1, 2025", "Jan 2, 2025", icu.fmt("2025-01-03[u-ca=hebrew]")),
+  )
+
+  // errors are reported per item
+  let results = icu.fmt-batch(((year: 2025, month: 1, day: 1), (hour: 10), (year: 2025, month: 13, day: 1)))
+  assert.eq(results.at(0), "Jan 1, 2025")
+  assert.eq(type(results.at(1)), dictionary)
+  assert(results.at(1).error.starts-with("Some values are missing"))
+  assert.eq(type(results.at(2)), dictionary)
+  assert("error" in results.at(2))
+
+  // malformed items only fail on their own
+  let results = icu.fmt-batch(((year: 2025, month: 1, day: 1), (year: "2025", month: 1, day: 2)))
+  assert.eq(results.at(0), "Jan 1, 2025")
+  assert.eq(type(results.at(1)), dictionary)
+  assert.eq(results.at(1).error, "CBOR deserialization error for item: invalid type: string \"2025\", expected i32")
+
+  let times = ((hour: 9, minute: 0, second: 0), (hour: 17, minute: 30, second: 0))
+  assert.eq(
+    icu.fmt-batch(times, zone: (offset: "+01")),
+    ("9:00\u{202f}AM GMT+1", "5:30\u{202f}PM GMT+1"),
+  )
+}
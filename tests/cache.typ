@@ -0,0 +1,21 @@
+#import "../typst/api.typ" as icu
+
+#{
+  // all items of a batch share a formatter, because they resolve to the same
+  // preferences (the calendar annotation selects the default calendar)
+  let dates = ("2025-02-01", "2025-02-02[u-ca=gregory]", (year: 2025, month: 2, day: 3))
+  let res = icu.fmt-batch(dates, locale: "EN-gb", length: "full", stats: true)
+  assert.eq(res.items.len(), 3)
+  // the first item is a hit if an earlier call created the formatter
+  assert.eq(res.stats.hits + res.stats.misses, 3)
+  assert(res.stats.hits >= 2)
+
+  let res = icu.fmt-batch((), stats: true)
+  assert.eq(res, (items: (), stats: (hits: 0, misses: 0)))
+
+  // best-effort diagnostics (the calls might not reach the same instance)
+  let stats = icu.cache-stats()
+  for cache in (stats.formatters, stats.patterns) {
+    assert(cache.entries <= cache.capacity)
+  }
+}
@@ -0,0 +1,26 @@
+#import "../typst/api.typ" as icu
+
+#{
+  let date = (year: 2025, month: 3, day: 14)
+  assert.eq(icu.fmt(date, calendar: "gregory"), "Mar 14, 2025")
+  // `calendar` overrides the calendar of the locale
+  assert.eq(icu.fmt(date, calendar: "gregory", locale: "en-u-ca-hebrew"), "Mar 14, 2025")
+  assert.eq(icu.fmt(date, calendar: "hebrew"), "Adar 14, 5785 AM")
+  assert.eq(icu.fmt(date, calendar: "japanese"), "Mar 14, 7 Reiwa")
+  assert.eq(icu.fmt(date, calendar: "islamic-umalqura"), "Ram. 14, 1446 AH")
+  assert.eq(icu.fmt(date, calendar: "persian"), "Esfand 24, 1403 AP")
+  assert.eq(icu.fmt(date, calendar: "hebrew"), icu.fmt(date, locale: "en-u-ca-hebrew"))
+  assert.eq(icu.fmt(date, calendar: "japanese"), icu.fmt(date, locale: "en-u-ca-japanese"))
+
+  // ... and the calendar of IXDTF strings
+  assert.eq(icu.fmt("2025-03-14[u-ca=hebrew]", calendar: "gregory"), "Mar 14, 2025")
+
+  assert.eq(icu.fmt(date, calendar: "hebrew", experimental-pattern: "y"), "5785")
+  assert.eq(icu.fmt(date, locale: "th", experimental-pattern: "y"), "2568")
+  assert.eq(icu.fmt(date, locale: "th", calendar: "gregory", experimental-pattern: "y"), "2025")
+
+  assert.eq(
+    icu.fmt-range(date, (year: 2025, month: 3, day: 20), calendar: "gregory", locale: "en-u-ca-hebrew"),
+    "Mar 14 – 20, 2025",
+  )
+}
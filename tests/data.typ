@@ -0,0 +1,17 @@
+#import "../typst/api.typ" as icu
+
+#{
+  // tests/data/de.postcard is exported by `just test-data`
+  let data = read("data/de.postcard", encoding: none)
+  let date = (year: 2025, month: 3, day: 14, hour: 9, minute: 30, second: 0)
+
+  assert.eq(icu.fmt(date, locale: "de", data: data), icu.fmt(date, locale: "de"))
+  assert.eq(icu.fmt(date, locale: "de-AT", length: "long", data: data), icu.fmt(date, locale: "de-AT", length: "long"))
+  assert.eq(icu.fmt-batch((date,), locale: "de", data: data), (icu.fmt(date, locale: "de"),))
+  assert(icu.fmt(date, locale: "de", strict: true, data: data).len() > 0)
+
+  // languages that aren't in the blob use the compiled data instead of the
+  // root locale of the blob
+  assert.eq(icu.fmt(date, locale: "fr", data: data), icu.fmt(date, locale: "fr"))
+  assert.eq(icu.fmt(date, locale: "ja", length: "long", data: data), icu.fmt(date, locale: "ja", length: "long"))
+}
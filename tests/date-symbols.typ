@@ -0,0 +1,39 @@
+#import "../typst/api.typ" as icu
+
+#{
+  let en = icu.date-symbols()
+  assert.eq(en.months.year, 2024)
+  assert.eq(en.months.codes.len(), 12)
+  assert.eq(en.months.codes.first(), "M01")
+  assert.eq(en.months.format.wide.first(), "January")
+  assert.eq(en.months.format.abbreviated.last(), "Dec")
+  assert.eq(en.months.format.narrow.slice(0, 3), ("J", "F", "M"))
+  assert.eq(
+    en.weekdays.format.wide,
+    ("Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"),
+  )
+  assert.eq(en.weekdays.format.short.first(), "Mo")
+  assert.eq(en.weekdays.standalone.abbreviated.first(), "Mon")
+  assert.eq(en.eras.map(it => it.code), ("bce", "ce"))
+  assert.eq(en.eras.map(it => it.abbreviated), ("BC", "AD"))
+  assert.eq(
+    en.day-periods.abbreviated,
+    (am: "AM", pm: "PM", noon: "noon", midnight: "midnight"),
+  )
+
+  // genitive vs. nominative
+  let fi = icu.date-symbols(locale: "fi")
+  assert.eq(fi.months.format.wide.first(), "tammikuuta")
+  assert.eq(fi.months.standalone.wide.first(), "tammikuu")
+
+  let hebrew = icu.date-symbols(calendar: "hebrew", year: 5784)
+  assert.eq(hebrew.months.codes.len(), 13)
+  assert("M05L" in hebrew.months.codes)
+  assert.eq(icu.date-symbols(calendar: "hebrew", year: 5785).months.codes.len(), 12)
+
+  let japanese = icu.date-symbols(calendar: "japanese")
+  assert.eq(japanese.eras.map(it => it.code).slice(-2), ("heisei", "reiwa"))
+  assert.eq(japanese.eras.last().wide, "Reiwa")
+
+  assert.eq(icu.date-symbols(calendar: "chinese").eras, ())
+}
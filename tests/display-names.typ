@@ -0,0 +1,20 @@
+#import "../typst/api.typ" as icu
+
+#{
+  let names = icu.display-names("de-Latn-CH")
+  assert.eq(names.language, "German")
+  assert.eq(names.script, "Latin")
+  assert.eq(names.region, "Switzerland")
+  assert.eq(names.variants, ())
+  assert.eq(names.locale, "Swiss High German (Latin)")
+
+  assert.eq(icu.display-names("de").language, "German")
+  assert.eq(icu.display-names("de", display-locale: "de").language, "Deutsch")
+  assert.eq(icu.display-names("de", display-locale: "fr").language, "allemand")
+  assert.eq(icu.display-names("en-GB").locale, "British English")
+  assert.eq(icu.display-names("en-GB", language-display: "standard").locale, "English (United Kingdom)")
+  assert.eq(icu.display-names("en-GB", style: "short").region, "UK")
+  assert.eq(icu.display-names("de-CH-1996").variants, ("Swiss German Orthography of 1996",))
+  assert.eq(icu.display-names("zh-Hant").script, "Traditional Han")
+  assert.eq(icu.display-names("ja").script, none)
+}
@@ -0,0 +1,26 @@
+#import "../typst/api.typ" as icu
+
+#{
+  let value = (hours: 1, minutes: 46, seconds: 40)
+  assert.eq(icu.fmt-duration(value, style: "long"), "1 hour, 46 minutes, 40 seconds")
+  assert.eq(icu.fmt-duration(value), "1 hr, 46 min, 40 sec")
+  assert.eq(icu.fmt-duration(value, style: "narrow"), "1h 46m 40s")
+  assert.eq(icu.fmt-duration(value, style: "digital"), "1:46:40")
+
+  // zero fields are omitted (except in the digital style)
+  assert.eq(icu.fmt-duration((hours: 2, minutes: 5, seconds: 0)), "2 hr, 5 min")
+  assert.eq(icu.fmt-duration((hours: 2, minutes: 5), style: "digital"), "2:05:00")
+
+  // fields aren't balanced
+  assert.eq(icu.fmt-duration((minutes: 90), style: "narrow"), "90m")
+
+  let precise = (minutes: 1, seconds: 2, milliseconds: 500)
+  assert.eq(icu.fmt-duration(precise, style: "digital"), "0:01:02.5")
+  assert.eq(icu.fmt-duration(precise, style: "digital", fractional-digits: 2), "0:01:02.50")
+  assert.eq(icu.fmt-duration(precise, style: "digital", fractional-digits: 0), "0:01:02")
+
+  assert.eq(icu.fmt-duration((hours: -2, minutes: -5), style: "narrow"), "-2h 5m")
+
+  assert.eq(icu.fmt-duration(duration(hours: 1, minutes: 2, seconds: 3), style: "digital"), "1:02:03")
+  assert.eq(icu.fmt-duration(duration(minutes: 30), style: "long"), "30 minutes")
+}
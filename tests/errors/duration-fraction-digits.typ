@@ -0,0 +1,4 @@
+// error: Invalid number of fraction digits in the duration options: there can be at most 9 digits
+#import "../../typst/api.typ" as icu
+
+#icu.fmt-duration((seconds: 1, milliseconds: 500), style: "digital", fractional-digits: 10)
@@ -0,0 +1,5 @@
+// error: The provided time zone (ID) will never have the provided offset - it has these offsets: standard=+01, daylight=+02
+#import "../../typst/api.typ" as icu
+
+// +05 is neither the standard nor the daylight offset of Berlin
+#icu.fmt((year: 2025, month: 7, day: 14), zone: (offset: "+05", iana: "Europe/Berlin"))
@@ -0,0 +1,19 @@
+#import "../typst/api.typ" as icu
+
+#{
+  // 15 Nisan 5785
+  assert.eq(icu.fmt((calendar: "hebrew", year: 5785, month-code: "M07", day: 15)), "Apr 13, 2025")
+  assert.eq(icu.fmt((calendar: "hebrew", year: 5785, month: 7, day: 15)), "Apr 13, 2025")
+  // Adar I in a leap year
+  assert.eq(icu.fmt((calendar: "hebrew", year: 5784, month-code: "M05L", day: 1)), "Feb 10, 2024")
+  // 1 Ramadan 1446
+  assert.eq(icu.fmt((calendar: "islamic-umalqura", year: 1446, month: 9, day: 1)), "Mar 1, 2025")
+  assert.eq(icu.fmt((calendar: "japanese", era: "reiwa", year: 7, month: 1, day: 1)), "Jan 1, 2025")
+  assert.eq(icu.fmt((calendar: "iso8601", year: 2025, month-code: "M03", day: 14)), "Mar 14, 2025")
+
+  // the input calendar is independent of the output calendar
+  assert.eq(
+    icu.fmt((calendar: "hebrew", year: 5785, month-code: "M07", day: 15), experimental-pattern: "y", locale: "en-u-ca-hebrew"),
+    "5785",
+  )
+}
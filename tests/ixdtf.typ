@@ -0,0 +1,45 @@
+#import "../typst/api.typ" as icu
+
+#{
+  assert.eq(icu.fmt("2024-05-17"), "May 17, 2024")
+  assert.eq(icu.fmt("20240517"), "May 17, 2024")
+  assert.eq(icu.fmt("T13:38:19"), "1:38\u{202f}PM")
+  assert.eq(icu.fmt("2024-05-17T13:38:19"), "May 17, 2024, 1:38\u{202f}PM")
+  assert.eq(icu.fmt("2024-05-17 13:38:19.012345678"), "May 17, 2024, 1:38\u{202f}PM")
+  assert.eq(icu.fmt("T13:38:19.5", experimental-pattern: "ss.SSS"), "19.500")
+  assert.eq(icu.fmt("2024-05-17T13:38:19+08:00"), "May 17, 2024, 1:38\u{202f}PM GMT+8")
+  assert.eq(icu.fmt("2024-05-17T13:38:19Z"), "May 17, 2024, 1:38\u{202f}PM GMT")
+  assert.eq(icu.fmt("2024-05-17T13:38:19[+08:00]"), "May 17, 2024, 1:38\u{202f}PM GMT+8")
+}
+
+#{
+  // the offset is resolved from the zone
+  assert.eq(icu.fmt("2025-03-14T09:30:00[Europe/Berlin]"), "Mar 14, 2025, 9:30\u{202f}AM GMT+1")
+  assert.eq(icu.fmt("2025-07-14T09:30:00[Europe/Berlin]"), "Jul 14, 2025, 9:30\u{202f}AM GMT+2")
+  assert.eq(
+    icu.fmt("2025-03-14T09:30:00+01:00[Europe/Berlin]", zone-style: "specific-long", time-precision: "minute"),
+    "9:30\u{202f}AM Central European Standard Time",
+  )
+  // `Z` only specifies the instant, the local time is taken from the zone
+  assert.eq(icu.fmt("2025-03-14T08:30:00Z[Europe/Berlin]"), "Mar 14, 2025, 9:30\u{202f}AM GMT+1")
+  assert.eq(icu.fmt("2025-03-14T23:30:00Z[Europe/Berlin]"), "Mar 15, 2025, 12:30\u{202f}AM GMT+1")
+}
+
+#{
+  // calendar annotation
+  let hebrew = "2025-03-14[u-ca=hebrew]"
+  assert.eq(icu.fmt(hebrew, experimental-pattern: "y"), "5785")
+  assert.eq(icu.fmt(hebrew, locale: "en-u-ca-gregory", experimental-pattern: "y"), "2025")
+  assert.eq(icu.fmt("2025-03-14[u-ca=gregory]", locale: "en-u-ca-gregory"), "Mar 14, 2025")
+  // `iso8601` is the same as no annotation
+  let iso = "2025-01-01[u-ca=iso8601]"
+  assert.eq(icu.fmt(iso), "Jan 1, 2025")
+  assert.eq(icu.fmt(iso, locale: "th", experimental-pattern: "y"), "2568")
+  assert.eq(icu.fmt(iso, calendar: "hebrew", experimental-pattern: "y"), "5785")
+}
+
+#{
+  // strings can be used wherever dates are accepted
+  assert.eq(icu.fmt-range("2025-01-03", "2025-01-07"), "Jan 3 – 7, 2025")
+  assert.eq(icu.fmt-relative("2025-01-05", relative-to: "2025-01-03"), "in 2 days")
+}
@@ -0,0 +1,16 @@
+#import "../typst/api.typ" as icu
+
+#{
+  let items = ("Alice", "Bob", "Carol")
+  assert.eq(icu.fmt-list(()), "")
+  assert.eq(icu.fmt-list(("Alice",)), "Alice")
+  assert.eq(icu.fmt-list(("Alice", "Bob")), "Alice and Bob")
+  assert.eq(icu.fmt-list(items), "Alice, Bob, and Carol")
+  assert.eq(icu.fmt-list(items, locale: "en-GB"), "Alice, Bob and Carol")
+  assert.eq(icu.fmt-list(items, kind: "or"), "Alice, Bob, or Carol")
+  assert.eq(icu.fmt-list(items, width: "short"), "Alice, Bob, & Carol")
+  assert.eq(icu.fmt-list(items, locale: "de"), "Alice, Bob und Carol")
+  assert.eq(icu.fmt-list(("Iago", "Oscar"), locale: "es"), "Iago y Oscar")
+  assert.eq(icu.fmt-list(("Oscar", "Iago"), locale: "es"), "Oscar e Iago")
+  assert.eq(icu.fmt-list(("5 pounds", "12 ounces"), kind: "unit"), "5 pounds, 12 ounces")
+}
@@ -0,0 +1,32 @@
+#import "../typst/api.typ" as icu
+
+#{
+  let en = icu.locale-fallback("en")
+  assert.eq(en.requested, "en")
+  assert.eq(en.resolved, "en")
+  assert(en.exact)
+  assert.eq(en.chain.last().locale, "und")
+
+  let unknown-region = icu.locale-fallback("de-XY")
+  assert.eq(unknown-region.requested, "de-XY")
+  assert.eq(unknown-region.resolved, "de")
+  assert(not unknown-region.exact)
+  assert.eq(unknown-region.chain.map(it => it.locale), ("de-XY", "de", "und"))
+  assert.eq(unknown-region.chain.map(it => it.has-data), (false, true, true))
+
+  let date = (year: 2025, month: 3, day: 14)
+  assert.eq(icu.fmt(date, locale: "de", strict: true), icu.fmt(date, locale: "de"))
+
+  // the data of "en" and "de" is used for their default regions
+  assert(icu.locale-fallback("en-US").exact)
+  assert(icu.locale-fallback("de-DE").exact)
+  assert.eq(icu.locale-fallback("de-DE").resolved, "de")
+  assert.eq(icu.fmt(date, locale: "en-US", strict: true), icu.fmt(date, locale: "en"))
+  assert.eq(icu.fmt(date, locale: "de-DE", strict: true), icu.fmt(date, locale: "de"))
+  assert.eq(icu.fmt-batch((date,), locale: "de-DE", strict: true), (icu.fmt(date, locale: "de"),))
+  assert.eq(icu.fmt(date, locale: "de-DE", strict: true, experimental-pattern: "y"), "2025")
+
+  // subdivisions don't have their own data
+  assert(icu.locale-fallback("de-DE-u-sd-debe").exact)
+  assert.eq(icu.fmt(date, locale: "de-DE-u-sd-debe", strict: true), icu.fmt(date, locale: "de"))
+}
@@ -0,0 +1,51 @@
+#import "../typst/api.typ" as icu
+
+#{
+  let id(locale, transform) = icu.locale-info(locale, transform: transform).id
+
+  assert.eq(id("iw", none).language, "iw")
+  assert.eq(id("iw", "canonicalize").language, "he")
+  assert.eq(id("sr-Latn", "canonicalize"), (language: "sr", script: "Latn", region: none, variants: ()))
+
+  assert.eq(id("zh-TW", "maximize"), (language: "zh", script: "Hant", region: "TW", variants: ()))
+  assert.eq(id("und", "maximize"), (language: "en", script: "Latn", region: "US", variants: ()))
+  assert.eq(id("iw", "maximize"), (language: "he", script: "Hebr", region: "IL", variants: ()))
+
+  assert.eq(id("zh-Hant-TW", "minimize"), (language: "zh", script: none, region: "TW", variants: ()))
+  assert.eq(id("sr-Cyrl-RS", "minimize"), (language: "sr", script: none, region: none, variants: ()))
+
+  let minimized = icu.locale-info("en-US-u-hc-h23", transform: "minimize")
+  assert.eq(minimized.id.region, none)
+  assert.eq(minimized.extensions.unicode.keywords, (hc: ("h23",)))
+
+  let keywords(locale) = icu.locale-info(locale).extensions.unicode.keywords
+  assert.eq(keywords("en"), (:))
+  assert.eq(keywords("en-u-ca-islamic-civil-hc-h12"), (ca: ("islamic", "civil"), hc: ("h12",)))
+
+  let prefs(locale) = icu.locale-info(locale).preferences
+  assert.eq(prefs("en-US"), (
+    region: "US",
+    hour-cycle: "h12",
+    calendar: "gregory",
+    first-day: "sun",
+    numbering-system: "latn",
+    measurement-system: "ussystem",
+  ))
+  assert.eq(prefs("de").region, "DE")
+  assert.eq(prefs("de").hour-cycle, "h23")
+  assert.eq(prefs("de").first-day, "mon")
+  assert.eq(prefs("de").measurement-system, "metric")
+  assert.eq(prefs("th").calendar, "buddhist")
+  assert.eq(prefs("ar-SA-u-ca-islamic-umalqura").calendar, "islamic-umalqura")
+  assert.eq(prefs("ar-u-ca-islamic-civil").calendar, "islamic-civil")
+  assert.eq(prefs("en-u-ca-ethiopic").calendar, "ethiopic")
+  assert.eq(prefs("ar-EG").numbering-system, "arab")
+
+  let overridden = prefs("en-US-u-hc-h23-rg-gbzzzz-fw-mon")
+  assert.eq(overridden.region, "GB")
+  assert.eq(overridden.hour-cycle, "h23")
+  assert.eq(overridden.first-day, "mon")
+  assert.eq(overridden.measurement-system, "uksystem")
+  assert.eq(prefs("en-US-u-rg-czzzzz").region, "CZ")
+  assert.eq(prefs("en-u-rg-mxzzzz").region, "MX")
+}
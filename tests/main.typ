@@ -1,7 +1,28 @@
 #include "auto-selection.typ"
+#include "batch.typ"
+#include "cache.typ"
+#include "calendar.typ"
+#include "data.typ"
+#include "date-symbols.typ"
 #include "date.typ"
+#include "display-names.typ"
+#include "duration.typ"
+#include "input-calendar.typ"
+#include "ixdtf.typ"
+#include "list.typ"
+#include "locale-fallback.typ"
+#include "locale-info.typ"
+#include "month-grid.typ"
+#include "number.typ"
+#include "parts.typ"
 #include "pattern.typ"
+#include "plural.typ"
+#include "range.typ"
+#include "relative.typ"
+#include "sort.typ"
 #include "time.typ"
+#include "to-calendar.typ"
+#include "week.typ"
 #include "zone.typ"
 
 #metadata("Okay :)") <ok>
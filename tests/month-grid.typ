@@ -0,0 +1,41 @@
+#import "../typst/api.typ" as icu
+
+#{
+  // Saturday, March 1st 2025
+  let us = icu.month-grid(2025, 3, locale: "en-US")
+  assert.eq(us.title, "March 2025")
+  assert.eq((us.year, us.month, us.month-code), (2025, 3, "M03"))
+  assert.eq(us.headers.map(it => it.weekday), ("sun", "mon", "tue", "wed", "thu", "fri", "sat"))
+  assert.eq(
+    us.headers.first(),
+    (weekday: "sun", narrow: "S", short: "Su", abbreviated: "Sun", wide: "Sunday", is-weekend: true),
+  )
+  assert.eq(us.weeks.len(), 6)
+  assert.eq(us.weeks.map(it => it.number), (9, 10, 11, 12, 13, 14))
+
+  let first-week = us.weeks.first().days
+  assert.eq(first-week.first(), (day: 23, label: "23", in-month: false, date: (year: 2025, month: 2, day: 23)))
+  assert.eq(first-week.last(), (day: 1, label: "1", in-month: true, date: (year: 2025, month: 3, day: 1)))
+  assert.eq(us.weeks.last().days.filter(it => it.in-month).map(it => it.day), (30, 31))
+  assert.eq(us.weeks.map(it => it.days.filter(it => it.in-month).len()).sum(), 31)
+
+  // weeks start on Monday
+  let de = icu.month-grid(2025, 3, locale: "de")
+  assert.eq(de.title, "März 2025")
+  assert.eq(de.headers.map(it => it.weekday).first(), "mon")
+  assert.eq(de.headers.map(it => it.is-weekend), (false,) * 5 + (true,) * 2)
+  assert.eq(de.weeks.map(it => it.number), (9, 10, 11, 12, 13, 14))
+  assert.eq(de.weeks.first().days.map(it => it.in-month), (false,) * 5 + (true,) * 2)
+
+  // Adar I 5784 (a leap month)
+  let adar = icu.month-grid(5784, "M05L", calendar: "hebrew")
+  assert.eq(adar.month-code, "M05L")
+  let first-day = adar.weeks.first().days.find(it => it.in-month)
+  assert.eq(first-day.day, 1)
+  assert.eq(first-day.date, (year: 2024, month: 2, day: 10))
+
+  assert.eq(
+    icu.month-grid(2025, 3, locale: "en-US", calendar: "gregory"),
+    us,
+  )
+}
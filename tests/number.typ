@@ -0,0 +1,30 @@
+#import "../typst/api.typ" as icu
+
+#{
+  assert.eq(icu.fmt-number(1234567), "1,234,567")
+  assert.eq(icu.fmt-number(-1234.5), "-1,234.5")
+  assert.eq(icu.fmt-number("1234.50"), "1,234.50")
+  assert.eq(icu.fmt-number(decimal("0.10")), "0.10")
+  assert.eq(icu.fmt-number(decimal("-1234.50")), "-1,234.50")
+  assert.eq(icu.fmt-number(1234567, locale: "de"), "1.234.567")
+  assert.eq(icu.fmt-number(1234567, locale: "hi"), "12,34,567")
+  assert.eq(icu.fmt-number(1234.5, locale: "fr"), "1\u{202f}234,5")
+  assert.eq(icu.fmt-number(12345, locale: "ar-EG"), "١٢٬٣٤٥")
+}
+
+#{
+  assert.eq(icu.fmt-number(1234, grouping: "never"), "1234")
+  assert.eq(icu.fmt-number(1234, grouping: "min2"), "1234")
+  assert.eq(icu.fmt-number(12345, grouping: "min2"), "12,345")
+
+  assert.eq(icu.fmt-number(3.14159, max-fraction-digits: 2), "3.14")
+  assert.eq(icu.fmt-number(2.5, max-fraction-digits: 0), "3")
+  assert.eq(icu.fmt-number(2.5, max-fraction-digits: 0, rounding-mode: "half-even"), "2")
+  assert.eq(icu.fmt-number(2.1, max-fraction-digits: 0, rounding-mode: "ceil"), "3")
+  assert.eq(icu.fmt-number(-2.1, max-fraction-digits: 0, rounding-mode: "floor"), "-3")
+  assert.eq(icu.fmt-number(3, min-fraction-digits: 2), "3.00")
+
+  assert.eq(icu.fmt-number(5, sign-display: "always"), "+5")
+  assert.eq(icu.fmt-number(0, sign-display: "except-zero"), "0")
+  assert.eq(icu.fmt-number(-5, sign-display: "never"), "5")
+}
@@ -0,0 +1,53 @@
+#import "../typst/api.typ" as icu
+
+#{
+  let date = (year: 2024, month: 5, day: 17)
+  assert.eq(
+    icu.fmt-parts(date, date-fields: "YMDE"),
+    (
+      (text: "Fri", part: "weekday"),
+      (text: ", ", part: "literal"),
+      (text: "May", part: "month"),
+      (text: " ", part: "literal"),
+      (text: "17", part: "day"),
+      (text: ", ", part: "literal"),
+      (text: "2024", part: "year"),
+    ),
+  )
+
+  let time = (hour: 13, minute: 38, second: 19)
+  assert.eq(
+    icu.fmt-parts(time, zone: (offset: "+08")),
+    (
+      (text: "1", part: "hour"),
+      (text: ":", part: "literal"),
+      (text: "38", part: "minute"),
+      (text: "\u{202f}", part: "literal"),
+      (text: "PM", part: "day-period"),
+      (text: " ", part: "literal"),
+      (text: "GMT+8", part: "time-zone-name"),
+    ),
+  )
+
+  // the parts make up the formatted string
+  let datetime = (..date, ..time)
+  for locale in ("en", "de", "ja", "ar") {
+    assert.eq(
+      icu.fmt-parts(datetime, locale: locale, length: "long").map(it => it.text).join(),
+      icu.fmt(datetime, locale: locale, length: "long"),
+    )
+  }
+
+  assert.eq(
+    icu.fmt-parts(date, experimental-pattern: "y-MM-dd G"),
+    (
+      (text: "2024", part: "year"),
+      (text: "-", part: "literal"),
+      (text: "05", part: "month"),
+      (text: "-", part: "literal"),
+      (text: "17", part: "day"),
+      (text: " ", part: "literal"),
+      (text: "AD", part: "era"),
+    ),
+  )
+}
@@ -0,0 +1,28 @@
+#import "../typst/api.typ" as icu
+
+#{
+  assert.eq(icu.plural-category(1), "one")
+  assert.eq(icu.plural-category(2), "other")
+  assert.eq(icu.plural-category("1.0"), "other")
+  assert.eq(icu.plural-category(-1), "one")
+  assert.eq(icu.plural-category(-1.5), "other")
+  assert.eq(icu.plural-category(decimal("-2")), "other")
+  assert.eq(icu.plural-category(0, locale: "fr"), "one")
+
+  assert.eq(icu.plural-category(2, locale: "pl"), "few")
+  assert.eq(icu.plural-category(5, locale: "pl"), "many")
+  assert.eq(icu.plural-category(22, locale: "pl"), "few")
+  assert.eq(icu.plural-category("1.5", locale: "pl"), "other")
+
+  assert.eq(icu.plural-category(0, locale: "ar"), "zero")
+  assert.eq(icu.plural-category(2, locale: "ar"), "two")
+  assert.eq(icu.plural-category(11, locale: "ar"), "many")
+}
+
+#{
+  assert.eq(icu.plural-category(1, kind: "ordinal"), "one")
+  assert.eq(icu.plural-category(2, kind: "ordinal"), "two")
+  assert.eq(icu.plural-category(3, kind: "ordinal"), "few")
+  assert.eq(icu.plural-category(11, kind: "ordinal"), "other")
+  assert.eq(icu.plural-category(1, kind: "ordinal", locale: "de"), "other")
+}
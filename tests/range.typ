@@ -0,0 +1,35 @@
+#import "../typst/api.typ" as icu
+
+#{
+  let jan3 = (year: 2025, month: 1, day: 3)
+  let jan7 = (year: 2025, month: 1, day: 7)
+  let feb2 = (year: 2025, month: 2, day: 2)
+  let next-year = (year: 2026, month: 1, day: 3)
+
+  assert.eq(icu.fmt-range(jan3, jan3), "Jan 3, 2025")
+  assert.eq(icu.fmt-range(jan3, jan7), "Jan 3 – 7, 2025")
+  assert.eq(icu.fmt-range(jan3, jan7, length: "long"), "January 3 – 7, 2025")
+  assert.eq(icu.fmt-range(jan3, jan7, date-fields: "YMDE"), "Fri, Jan 3 – Tue, Jan 7, 2025")
+  assert.eq(icu.fmt-range(jan3, feb2, length: "long"), "January 3 – February 2, 2025")
+  assert.eq(icu.fmt-range(jan3, next-year), "Jan 3, 2025 – Jan 3, 2026")
+  assert.eq(icu.fmt-range(jan3, feb2, date-fields: "Y"), "2025")
+  assert.eq(icu.fmt-range(jan3, next-year, date-fields: "Y"), "2025 – 2026")
+  assert.eq(icu.fmt-range(jan3, feb2, date-fields: "YM"), "Jan – Feb 2025")
+  // the interval fallback of the locale
+  assert.eq(icu.fmt-range(jan3, next-year, date-fields: "Y", locale: "fi"), "2025–2026")
+
+  let morning = (..jan3, hour: 10, minute: 0, second: 0)
+  let noon = (..jan3, hour: 12, minute: 0, second: 0)
+  assert.eq(icu.fmt-range(morning, noon), "Jan 3, 2025, 10:00\u{202f}AM – 12:00\u{202f}PM")
+  let half-past = (..jan3, hour: 10, minute: 30, second: 0)
+  assert.eq(icu.fmt-range(morning, half-past), "Jan 3, 2025, 10:00 – 10:30\u{202f}AM")
+  let next-morning = (..jan7, hour: 9, minute: 0, second: 0)
+  assert.eq(
+    icu.fmt-range(morning, next-morning),
+    "Jan 3, 2025, 10:00\u{202f}AM – Jan 7, 2025, 9:00\u{202f}AM",
+  )
+  assert.eq(
+    icu.fmt-range(morning, noon, zone: (offset: "+01")),
+    "Jan 3, 2025, 10:00\u{202f}AM – 12:00\u{202f}PM GMT+1",
+  )
+}
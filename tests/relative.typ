@@ -0,0 +1,23 @@
+#import "../typst/api.typ" as icu
+
+#{
+  assert.eq(icu.fmt-relative(-3, unit: "day"), "3 days ago")
+  assert.eq(icu.fmt-relative(2, unit: "hour"), "in 2 hours")
+  assert.eq(icu.fmt-relative(1, unit: "day"), "in 1 day")
+  assert.eq(icu.fmt-relative(1, unit: "day", numeric: "auto"), "tomorrow")
+  assert.eq(icu.fmt-relative(-1, unit: "day", numeric: "auto"), "yesterday")
+  assert.eq(icu.fmt-relative(3, unit: "week", style: "short"), "in 3 wk.")
+  assert.eq(icu.fmt-relative(-1, unit: "day", numeric: "auto", locale: "de"), "gestern")
+}
+
+#{
+  let date = (year: 2025, month: 3, day: 14)
+  assert.eq(icu.fmt-relative((year: 2025, month: 3, day: 11), relative-to: date), "3 days ago")
+  assert.eq(icu.fmt-relative((year: 2025, month: 4, day: 4), relative-to: date), "in 3 weeks")
+  assert.eq(icu.fmt-relative((year: 2025, month: 6, day: 14), relative-to: date), "in 3 months")
+  assert.eq(icu.fmt-relative((year: 2023, month: 1, day: 1), relative-to: date), "2 years ago")
+
+  let dt = (..date, hour: 12, minute: 0, second: 0)
+  assert.eq(icu.fmt-relative((..date, hour: 14, minute: 30, second: 0), relative-to: dt), "in 2 hours")
+  assert.eq(icu.fmt-relative((..date, hour: 11, minute: 15, second: 0), relative-to: dt), "45 minutes ago")
+}
@@ -0,0 +1,20 @@
+#import "../typst/api.typ" as icu
+
+#{
+  let words = ("zebra", "Äpfel", "apple", "Zürich", "Ärger", "ant")
+  assert.eq(icu.sort(words), ("ant", "apple", "Äpfel", "Ärger", "zebra", "Zürich"))
+  assert.eq(icu.sort(words, locale: "sv"), ("ant", "apple", "zebra", "Zürich", "Äpfel", "Ärger"))
+
+  assert.eq(icu.sort(("b", "a", "c"), indices: true), (1, 0, 2))
+  assert.eq(icu.sort(("item 10", "item 2", "item 1")), ("item 1", "item 10", "item 2"))
+  assert.eq(icu.sort(("item 10", "item 2", "item 1"), numeric: true), ("item 1", "item 2", "item 10"))
+
+  assert.eq(icu.sort(("a", "A", "b", "B")), ("a", "A", "b", "B"))
+  assert.eq(icu.sort(("a", "A", "b", "B"), case-first: "upper"), ("A", "a", "B", "b"))
+
+  assert.eq(icu.sort(("ñu", "nube", "oso"), locale: "es"), ("nube", "ñu", "oso"))
+  assert.eq(icu.sort(("co-op", "coop", "co op"), alternate-handling: "shifted", strength: "primary"), ("co-op", "coop", "co op"))
+
+  let people = ((name: "Öberg"), (name: "Olsen"), (name: "Zimmer"))
+  assert.eq(icu.sort(people, key: p => p.name, locale: "sv").map(p => p.name), ("Olsen", "Zimmer", "Öberg"))
+}
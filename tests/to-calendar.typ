@@ -0,0 +1,56 @@
+#import "../typst/api.typ" as icu
+
+#{
+  let date = (year: 2025, month: 4, day: 13)
+
+  let hebrew = icu.to-calendar(date, "hebrew")
+  assert.eq(hebrew.era, "am")
+  assert.eq(hebrew.era-year, 5785)
+  assert.eq(hebrew.extended-year, 5785)
+  assert.eq(hebrew.month-code, "M07")
+  assert.eq(hebrew.ordinal-month, 7)
+  assert.eq(hebrew.is-leap-month, false)
+  assert.eq(hebrew.day, 15)
+  assert.eq(hebrew.day-of-week, 7)
+  assert.eq(hebrew.months-in-year, 12)
+  assert.eq(hebrew.is-leap-year, false)
+  assert.eq(hebrew.cyclic-year, none)
+
+  // Adar I
+  let adar1 = icu.to-calendar((year: 2024, month: 2, day: 10), "hebrew")
+  assert.eq(adar1.month-code, "M05L")
+  assert.eq(adar1.ordinal-month, 6)
+  assert.eq(adar1.is-leap-month, true)
+  assert.eq(adar1.months-in-year, 13)
+  assert.eq(adar1.is-leap-year, true)
+
+  let gregory = icu.to-calendar(date, "gregory")
+  assert.eq(gregory.era, "ce")
+  assert.eq(gregory.era-year, 2025)
+  assert.eq(gregory.day-of-year, 103)
+  assert.eq(gregory.days-in-month, 30)
+  assert.eq(gregory.days-in-year, 365)
+
+  assert.eq(icu.to-calendar(date, "iso8601").extended-year, 2025)
+  assert.eq(icu.to-calendar(date, "japanese").era, "reiwa")
+  assert.eq(icu.to-calendar(date, "japanese").era-year, 7)
+  assert.eq(icu.to-calendar((year: 2025, month: 3, day: 1), "islamic-umalqura").month-code, "M09")
+  assert.eq(icu.to-calendar(datetime(year: 2025, month: 3, day: 1), "islamic-umalqura").day, 1)
+  assert.eq(icu.to-calendar("2025-03-01T10:00:00", "islamic-umalqura").extended-year, 1446)
+
+  // 2025 is the year of the snake (yi-si, 42nd year of the cycle)
+  let chinese = icu.to-calendar(date, "chinese")
+  assert.eq(chinese.era, none)
+  assert.eq(chinese.cyclic-year, 42)
+  assert.eq(chinese.related-iso, 2025)
+}
+
+#{
+  // round trip
+  let hebrew = icu.to-calendar((year: 2025, month: 4, day: 13), "hebrew")
+  let back = icu.to-calendar(
+    (calendar: "hebrew", year: hebrew.extended-year, month-code: hebrew.month-code, day: hebrew.day),
+    "gregory",
+  )
+  assert.eq((back.era-year, back.ordinal-month, back.day), (2025, 4, 13))
+}
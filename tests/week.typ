@@ -0,0 +1,37 @@
+#import "../typst/api.typ" as icu
+
+#{
+  let us = icu.week-info(locale: "en-US")
+  assert.eq(us.first-day, "sun")
+  assert.eq(us.weekend, ("sat", "sun"))
+  assert.eq(us.min-days, 1)
+  assert.eq(us.week, none)
+
+  let de = icu.week-info(locale: "de")
+  assert.eq(de.first-day, "mon")
+  assert.eq(de.weekend, ("sat", "sun"))
+  assert.eq(de.min-days, 4)
+
+  assert.eq(icu.week-info(locale: "ar-EG").weekend, ("fri", "sat"))
+  assert.eq(icu.week-info(locale: "en-US-u-fw-mon").first-day, "mon")
+
+  // the region of `-u-rg` can end with "x" or "z" like the subdivision suffix
+  assert.eq(icu.week-info(locale: "en-US-u-rg-czzzzz").min-days, 4)
+  assert.eq(icu.week-info(locale: "en-US-u-rg-axzzzz").min-days, 4)
+  assert.eq(icu.week-info(locale: "de-u-rg-mxzzzz").min-days, 1)
+
+  // Friday, January 1st 2021 is in the last week of 2020 in ISO weeks
+  let new-year = icu.week-info(dt: (year: 2021, month: 1, day: 1), locale: "en-US").week
+  assert.eq(new-year.iso, (week-of-year: 53, week-year: 2020, week-of-month: 0))
+  assert.eq(new-year.locale, (week-of-year: 1, week-year: 2021, week-of-month: 1))
+
+  // Monday, December 29th 2025 is in the first week of 2026
+  let year-end = icu.week-info(dt: datetime(year: 2025, month: 12, day: 29), locale: "de").week
+  assert.eq(year-end.iso, (week-of-year: 1, week-year: 2026, week-of-month: 5))
+  assert.eq(year-end.locale, year-end.iso)
+
+  let mid-year = icu.week-info(dt: "2025-06-18", locale: "en-US").week
+  assert.eq(mid-year.iso.week-of-year, 25)
+  assert.eq(mid-year.locale.week-of-year, 25)
+  assert.eq(mid-year.locale.week-of-month, 3)
+}
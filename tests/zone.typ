@@ -88,3 +88,51 @@
     "Adelaide Time",
   )
 }
+
+#{
+  // offsets resolved from the TZDB
+  let winter = (year: 2025, month: 1, day: 15)
+  let summer = (year: 2025, month: 7, day: 15)
+  let berlin = (iana: "Europe/Berlin")
+  assert.eq(icu.fmt(winter, zone: berlin, zone-style: "localized-offset-short"), "GMT+1")
+  assert.eq(icu.fmt(summer, zone: berlin, zone-style: "localized-offset-short"), "GMT+2")
+  assert.eq(icu.fmt(winter, zone: berlin, zone-style: "specific-long"), "Central European Standard Time")
+  assert.eq(icu.fmt(summer, zone: berlin, zone-style: "specific-long"), "Central European Summer Time")
+  assert.eq(icu.fmt(summer, zone: (bcp47: "deber"), zone-style: "localized-offset-short"), "GMT+2")
+  assert.eq(icu.fmt(summer, zone: (iana: "Asia/Kolkata"), zone-style: "localized-offset-short"), "GMT+5:30")
+
+  // 02:30 doesn't exist on 2025-03-30 in Berlin
+  let gap = (year: 2025, month: 3, day: 30, hour: 2, minute: 30, second: 0)
+  let f(dt, disambiguation) = icu.fmt(
+    dt,
+    zone: (..berlin, disambiguation: disambiguation),
+    zone-style: "localized-offset-short",
+    time-precision: "minute",
+  )
+  // the time is moved by the length of the gap
+  assert.eq(f(gap, "compatible"), "3:30\u{202f}AM GMT+2")
+  assert.eq(f(gap, "earlier"), "1:30\u{202f}AM GMT+1")
+  assert.eq(f(gap, "later"), "3:30\u{202f}AM GMT+2")
+
+  // 02:30 exists twice on 2025-10-26 in Berlin
+  let overlap = (year: 2025, month: 10, day: 26, hour: 2, minute: 30, second: 0)
+  assert.eq(f(overlap, "compatible"), "2:30\u{202f}AM GMT+2")
+  assert.eq(f(overlap, "earlier"), "2:30\u{202f}AM GMT+2")
+  assert.eq(f(overlap, "later"), "2:30\u{202f}AM GMT+1")
+}
+
+#{
+  // the offset is checked against the zone (standard and daylight offsets are accepted,
+  // see errors/zone-offset-mismatch.typ for other offsets)
+  let date = (year: 2025, month: 7, day: 15)
+  assert.eq(icu.fmt(date, zone: (offset: "+01", iana: "Europe/Berlin"), zone-style: "localized-offset-short"), "GMT+1")
+  assert.eq(icu.fmt(date, zone: (offset: "+02", iana: "Europe/Berlin"), zone-style: "localized-offset-short"), "GMT+2")
+  assert.eq(
+    icu.fmt(
+      date,
+      zone: (offset: "+05", iana: "Europe/Berlin", validate-offset: false),
+      zone-style: "localized-offset-short",
+    ),
+    "GMT+5",
+  )
+}
@@ -1,2 +1,2 @@
-#import "impl.typ": fmt, locale-info
+#import "impl.typ": cache-stats, date-symbols, display-names, fmt, fmt-batch, fmt-duration, fmt-list, fmt-number, fmt-parts, fmt-range, fmt-relative, locale-fallback, locale-info, month-grid, plural-category, sort, to-calendar, week-info
 #import "constants.typ": alignment, date-fields, length, time-precision, year-styles, zone-styles
@@ -1,6 +1,15 @@
 #let plug = plugin("icu-datetime.wasm")
 
-/// Creates a dictionary from a datetime or echos a dictionary passed as `dt`.
+/// Gets the plugin with the locale `data` (a postcard blob) loaded or the plugin with the compiled data if `data` is `none`.
+#let plugin-with-data(data) = {
+  if data == none {
+    return plug
+  }
+  assert(type(data) == bytes, message: "`data` must be bytes - use `read(..., encoding: none)`")
+  plugin.transition(plug.load_data, data)
+}
+
+/// Creates a dictionary from a datetime or echos a dictionary or an IXDTF string passed as `dt`.
 #let datetime-to-dict(dt) = {
   if type(dt) == datetime {
     (
@@ -11,13 +20,148 @@
       minute: dt.minute(),
       second: dt.second(),
     )
-  } else if type(dt) == dictionary {
+  } else if type(dt) == dictionary or type(dt) == str {
     dt
   } else {
-    panic("Invalid datetime specification - expected type datetime or dictionary - got " + type(dt))
+    panic("Invalid datetime specification - expected type datetime, dictionary, or str - got " + type(dt))
   }
 }
 
+/// Adds `zone` to the specification `spec` (if any).
+#let insert-zone(spec, zone) = {
+  if zone != none {
+    assert(type(spec) != str, message: "`zone` can't be combined with a string - add the offset and zone to the string instead")
+    spec.insert("zone", zone)
+  }
+  spec
+}
+
+/// Checks which parts (date, time, and zone) are present in `spec`.
+#let spec-parts(spec) = {
+  if type(spec) == str {
+    // only a rough check, the string is parsed by the plugin
+    let date = regex("^([+-]\\d{6}|\\d{4})-?\\d{2}-?\\d{2}")
+    let rest = spec.replace(date, "")
+    return (
+      date: spec.match(date) != none,
+      time: rest.match(regex("^[Tt ]?\\d{2}")) != none,
+      zone: rest.match(regex("\\d[Zz]|\\d[+\u{2212}-]\\d{2}|\\[!?[^=\\]]+\\]")) != none,
+    )
+  }
+
+  (
+    date: (
+      spec.at("year", default: none) != none
+        and (spec.at("month", default: none) != none or spec.at("month-code", default: none) != none)
+        and spec.at("day", default: none) != none
+    ),
+    time: (
+      spec.at("hour", default: none) != none
+        and spec.at("minute", default: none) != none
+        and spec.at("second", default: none) != none
+    ),
+    zone: spec.at("zone", default: none) != none,
+  )
+}
+
+/// Creates the options for `format` and selects the fields if `date-fields`,
+/// `time-precision`, and `zone-style` are all `auto` (see `fmt`).
+#let resolve-opts(
+  spec,
+  length: none,
+  date-fields: auto,
+  time-precision: auto,
+  zone-style: auto,
+  alignment: none,
+  year-style: none,
+  calendar: none,
+  strict: false,
+) = {
+  // only pick a format if all three are `auto`
+  if date-fields == auto and time-precision == auto and zone-style == auto {
+    let parts = spec-parts(spec)
+
+    if parts.date {
+      date-fields = "YMD"
+    }
+    if parts.time {
+      time-precision = "minute"
+    }
+    if parts.zone {
+      zone-style = "localized-offset-short"
+    }
+  }
+
+  if date-fields == auto {
+    date-fields = none
+  }
+  if time-precision == auto {
+    time-precision = none
+  }
+  if zone-style == auto {
+    zone-style = none
+  }
+
+  (
+    length: length,
+    date-fields: date-fields,
+    time-precision: time-precision,
+    zone-style: zone-style,
+    alignment: alignment,
+    year-style: year-style,
+    calendar: calendar,
+    strict: strict,
+  )
+}
+
+/// Shared implementation of `fmt` and `fmt-parts`. Returns the bytes from the
+/// plugin - either the string or the CBOR encoded parts.
+#let format-impl(
+  dt,
+  parts,
+  zone: none,
+  locale: "en",
+  length: none,
+  date-fields: auto,
+  time-precision: auto,
+  zone-style: auto,
+  alignment: none,
+  year-style: none,
+  calendar: none,
+  strict: false,
+  experimental-pattern: none,
+  data: none,
+) = {
+  assert(type(locale) == str)
+
+  let plug = plugin-with-data(data)
+  let spec = insert-zone(datetime-to-dict(dt), zone)
+
+  if experimental-pattern != none {
+    let format-pattern = if parts { plug.format_pattern_to_parts } else { plug.format_pattern }
+    return format-pattern(
+      cbor.encode(spec),
+      bytes(locale),
+      bytes(experimental-pattern),
+      cbor.encode((calendar: calendar, strict: strict)),
+    )
+  }
+
+  let opts = resolve-opts(
+    spec,
+    length: length,
+    date-fields: date-fields,
+    time-precision: time-precision,
+    zone-style: zone-style,
+    alignment: alignment,
+    year-style: year-style,
+    calendar: calendar,
+    strict: strict,
+  )
+  let format = if parts { plug.format_to_parts } else { plug.format }
+  format(cbor.encode(spec), bytes(locale), cbor.encode(opts))
+}
+
 /// Formats a date, time, or timezone.
 ///
 /// If `date-fields`, `time-precision`, and `zone-style` all use their default values (`auto`),
@@ -26,8 +170,8 @@
 /// - If `dt` has time fields, then `time-precision` will be set to "minute"
 /// - If `zone` has a value, then `zone-style` will be set to "localized-offset-short"
 ///
-/// - dt (dictionary, datetime): The date and time to format. This can be a `datetime` or a dictionary with `year`, `month`, `day`, `hour`, `minute`, `second`, and (optionally) `nanosecond`.
-/// - zone (dictionary, none): The timezone. A dictionary with `offset`, `iana`, `bcp47`, `metazone-id`, and `zone-variant`. The options correspond to the arguments for `fmt-timezone`. Only `offset` is mandatory - the other fields provide supplemental information for named timezones.
+/// - dt (dictionary, datetime, str): The date and time to format. This can be a `datetime`, a dictionary with `year`, `month`, `day`, `hour`, `minute`, `second`, and (optionally) `nanosecond`, or an IXDTF string (RFC 9557) such as "2025-03-14T09:30:00+01:00[Europe/Berlin][u-ca=hebrew]". The offset, time zone, and calendar (`u-ca`) of the string are used. `calendar` and a calendar in `locale` take precedence over the one in the string. Dictionaries can specify the date in another calendar with `calendar` (e.g. "hebrew"), `era`, and `month-code` (e.g. "M05L" for a leap month) instead of `month`.
+/// - zone (dictionary, none): The timezone. A dictionary with `offset`, `iana`, `bcp47`, and `disambiguation`. If `offset` is omitted, it's resolved from the IANA time zone database for the date and time in `dt` (`dt` must have a date). `disambiguation` selects the point in time for times that don't exist or exist twice in the zone: "compatible" (default), "earlier", "later", or "reject". Times that don't exist are moved by the length of the gap. If `offset` is specified, it's checked against the standard and daylight offsets of the zone at the date in `dt` unless `validate-offset` is `false`.
 /// - locale (str): A Unicode Locale Identifier (see https://unicode.org/reports/tr35/tr35.html#Unicode_locale_identifier)
 /// - length (str, none): The length of the formatted date part ("long", "medium" (default), "short", or `none`). The avialable options are also provided in `length` as a dictionary.
 /// - date-fields (str, none, auto): The fields of the date to include in the formatted string. "D" (day of month), "MD", "YMD", "DE", "MDE", "YMDE", "E" (weekday), "M" (month), "YM", "Y" (year), `none`, or `auto` (default, see function documentation).
@@ -35,7 +179,10 @@
 /// - zone-style (str, none): How to format the timezone (if any). "specific-long", "specific-short", "localized-offset-long", "localized-offset-short",  "generic-long", "generic-short", "location", "exemplar-city", `none`, or `auto` (default, see function documentation).
 /// - alignment (str, none): How to align (pad) the formatted string. "auto", "column", or `none` (default, implies "auto").
 /// - year-style (str, none): How to format the year and the era. "auto", "full", "with-era", `none` (default, implies "auto").
-/// - experimental-pattern (str, none): Specifies the pattern to format that date as. This is mutually exclusive with all other named arguments except `zone` and `locale`. This argument is experimental. The calendar selection is implemented manually due to missing functionality in ICU4X. **This is a low-level utility that assumes the pattern is already localized for the target locale.** The full list of placeholders can be found on https://unicode.org/reports/tr35/tr35-dates.html#table-date-field-symbol-table. Note that this argument doesn't check that the date and time are fully specified. If some fields are left out, they're default initialized.
+/// - calendar (str, none): The calendar to format the date in. This overrides the calendar of `locale`. "buddhist", "chinese", "coptic", "dangi", "ethioaa", "ethiopic", "gregory", "hebrew", "indian", "islamic-civil", "islamic-tbla", "islamic-umalqura", "japanese", "persian", "roc", or `none` (default, implies the calendar of `locale`). Other calendars (such as "iso8601") result in an error.
+/// - strict (bool): Whether to fail if there's no date/time data for `locale` itself (data for an equivalent locale by the likely subtags, e.g. "en" for "en-US", counts). By default, ICU4X silently falls back to a parent locale (e.g. "de" for "de-XY") and ultimately to the root locale. See `locale-fallback`.
+/// - experimental-pattern (str, none): Specifies the pattern to format that date as. This is mutually exclusive with all other named arguments except `zone`, `locale`, `calendar`, `strict`, and `data`. This argument is experimental. The calendar selection is implemented manually due to missing functionality in ICU4X. **This is a low-level utility that assumes the pattern is already localized for the target locale.** The full list of placeholders can be found on https://unicode.org/reports/tr35/tr35-dates.html#table-date-field-symbol-table. Note that this argument doesn't check that the date and time are fully specified. If some fields are left out, they're default initialized.
+/// - data (bytes, none): A postcard data blob (exported with `icu4x-datagen --format blob`) to take the locale data from instead of the data that's compiled into the plugin. Read it with `read("data.postcard", encoding: none)`. Data and languages that are missing from the blob are taken from the compiled data. Only the date/time formatting uses the blob.
 #let fmt(
   dt,
   zone: none,
@@ -46,70 +193,434 @@
   zone-style: auto,
   alignment: none,
   year-style: none,
+  calendar: none,
+  strict: false,
   experimental-pattern: none,
-) = {
-  assert(type(locale) == str)
+  data: none,
+) = str(format-impl(
+  dt,
+  false,
+  zone: zone,
+  locale: locale,
+  length: length,
+  date-fields: date-fields,
+  time-precision: time-precision,
+  zone-style: zone-style,
+  alignment: alignment,
+  year-style: year-style,
+  calendar: calendar,
+  strict: strict,
+  experimental-pattern: experimental-pattern,
+  data: data,
+))
 
-  let spec = datetime-to-dict(dt)
-  if zone != none {
-    spec.insert("zone", zone)
-  }
+/// Formats a date, time, or timezone and splits the result into its parts.
+///
+/// Returns an array of dictionaries with `text` and `part`. `part` is one of "era", "year", "related-year", "year-name", "month", "day", "weekday", "day-period", "hour", "minute", "second", "time-zone-name", or "literal" (text that's not part of a field). Concatenating all `text`s results in the same string as `fmt`.
+///
+/// The arguments are the same as for `fmt`.
+#let fmt-parts(
+  dt,
+  zone: none,
+  locale: "en",
+  length: none,
+  date-fields: auto,
+  time-precision: auto,
+  zone-style: auto,
+  alignment: none,
+  year-style: none,
+  calendar: none,
+  strict: false,
+  experimental-pattern: none,
+  data: none,
+) = cbor(format-impl(
+  dt,
+  true,
+  zone: zone,
+  locale: locale,
+  length: length,
+  date-fields: date-fields,
+  time-precision: time-precision,
+  zone-style: zone-style,
+  alignment: alignment,
+  year-style: year-style,
+  calendar: calendar,
+  strict: strict,
+  experimental-pattern: experimental-pattern,
+  data: data,
+))
 
-  if experimental-pattern != none {
-    return str(plug.format_pattern(cbor.encode(spec), bytes(locale), bytes(experimental-pattern)))
+/// Formats many dates, times, or timezones with the same options.
+///
+/// This is faster than calling `fmt` for each item, because the formatter is only created once. The arguments are the same as for `fmt` (except `experimental-pattern`). The defaults (see `fmt`) are selected based on the first item.
+///
+/// Returns an array with the formatted strings. Items that couldn't be formatted are a dictionary with the `error` message instead.
+///
+/// - dts (array): The dates and times to format. Each item can be anything that's accepted by `fmt` (`datetime`, dictionary, or string).
+/// - stats (bool): If `true`, returns a dictionary with the formatted `items` and the `stats` of this call (the `hits` and `misses` of the formatter cache).
+#let fmt-batch(
+  dts,
+  zone: none,
+  locale: "en",
+  length: none,
+  date-fields: auto,
+  time-precision: auto,
+  zone-style: auto,
+  alignment: none,
+  year-style: none,
+  calendar: none,
+  strict: false,
+  data: none,
+  stats: false,
+) = {
+  assert(type(locale) == str)
+  assert(type(dts) == array)
+  if dts.len() == 0 {
+    return if stats { (items: (), stats: (hits: 0, misses: 0)) } else { () }
   }
 
-  // only pick a format if all three are `auto`
-  if date-fields == auto and time-precision == auto and zone-style == auto {
-    let has-date = (
-      spec.at("year", default: none) != none
-        and spec.at("month", default: none) != none
-        and spec.at("day", default: none) != none
-    )
-    let has-time = (
-      spec.at("hour", default: none) != none
-        and spec.at("minute", default: none) != none
-        and spec.at("second", default: none) != none
-    )
-    let has-zone = zone != none
+  let specs = dts.map(dt => insert-zone(datetime-to-dict(dt), zone))
+  let opts = resolve-opts(
+    specs.first(),
+    length: length,
+    date-fields: date-fields,
+    time-precision: time-precision,
+    zone-style: zone-style,
+    alignment: alignment,
+    year-style: year-style,
+    calendar: calendar,
+    strict: strict,
+  )
+  opts.insert("stats", stats)
+  cbor(plugin-with-data(data).format_batch(cbor.encode(specs), bytes(locale), cbor.encode(opts)))
+}
 
-    if has-date {
-      date-fields = "YMD"
-    }
-    if has-time {
-      time-precision = "minute"
-    }
-    if has-zone {
-      zone-style = "localized-offset-short"
-    }
-  }
+/// Formats the range between two dates, times, or datetimes such as "Jan 3 – 7, 2025".
+///
+/// Fields that both ends have in common are only displayed once. The ends are joined with `{0} – {1}` or a built-in interval fallback for some locales (ICU4X doesn't have interval data yet). The arguments are the same as for `fmt`. Both ends share the same `zone` and must be in the same calendar. `start` must not be after `end`.
+///
+/// - start (dictionary, datetime, str): The start of the range.
+/// - end (dictionary, datetime, str): The end of the range.
+#let fmt-range(
+  start,
+  end,
+  zone: none,
+  locale: "en",
+  length: none,
+  date-fields: auto,
+  time-precision: auto,
+  zone-style: auto,
+  alignment: none,
+  year-style: none,
+  calendar: none,
+  strict: false,
+  data: none,
+) = {
+  assert(type(locale) == str)
 
-  if date-fields == auto {
-    date-fields = none
-  }
-  if time-precision == auto {
-    time-precision = none
-  }
-  if zone-style == auto {
-    zone-style = none
-  }
+  let from = insert-zone(datetime-to-dict(start), zone)
+  let to = insert-zone(datetime-to-dict(end), zone)
 
-  let opts = (
+  let opts = resolve-opts(
+    from,
     length: length,
     date-fields: date-fields,
     time-precision: time-precision,
     zone-style: zone-style,
     alignment: alignment,
     year-style: year-style,
+    calendar: calendar,
+    strict: strict,
   )
-  str(plug.format(cbor.encode(spec), bytes(locale), cbor.encode(opts)))
+  str(plugin-with-data(data).format_range(cbor.encode(from), cbor.encode(to), bytes(locale), cbor.encode(opts)))
+}
+
+/// Formats a relative time such as "3 days ago" or "in 2 hours".
+///
+/// - value (int, dictionary, datetime, str): Either the signed amount of `unit`s (negative values are in the past) or - if `relative-to` is set - the date and time to describe.
+/// - unit (str, none): The unit of `value` if it's an integer. "second", "minute", "hour", "day", "week", "month", "quarter", or "year".
+/// - relative-to (dictionary, datetime, str, none): The date and time that `value` is relative to. If set, the unit is picked automatically.
+/// - locale (str): A Unicode Locale Identifier (see https://unicode.org/reports/tr35/tr35.html#Unicode_locale_identifier)
+/// - style (str): The length of the unit. "long" (default), "short", or "narrow".
+/// - numeric (str): "always" (default) to always use numbers ("in 1 day") or "auto" to use phrases like "tomorrow" where available.
+#let fmt-relative(
+  value,
+  unit: none,
+  relative-to: none,
+  locale: "en",
+  style: "long",
+  numeric: "always",
+) = {
+  assert(type(locale) == str)
+
+  let spec = if relative-to != none {
+    (from: datetime-to-dict(relative-to), to: datetime-to-dict(value))
+  } else {
+    assert(type(value) == int, message: "Expected an integer amount when `relative-to` is not set")
+    assert(unit != none, message: "A unit is required when `relative-to` is not set")
+    (amount: value, unit: unit)
+  }
+
+  let opts = (
+    style: style,
+    numeric: numeric,
+  )
+  str(plug.format_relative(cbor.encode(spec), bytes(locale), cbor.encode(opts)))
+}
+
+/// Formats a duration such as "2 hr, 5 min" or "1:02:03" (like `Intl.DurationFormat` in JavaScript).
+///
+/// - value (dictionary, duration): The duration. Either a dictionary with `years`, `months`, `weeks`, `days`, `hours`, `minutes`, `seconds`, `milliseconds`, `microseconds`, and `nanoseconds` (all optional integers with the same sign, other keys are an error) or a Typst `duration` (formatted as hours, minutes, and seconds). Fields are not balanced (e.g. 90 minutes stay 90 minutes).
+/// - locale (str): A Unicode Locale Identifier (see https://unicode.org/reports/tr35/tr35.html#Unicode_locale_identifier)
+/// - style (str): How to display the units. "long" ("2 hours, 5 minutes"), "short" (default, "2 hr, 5 min"), "narrow" ("2h 5m"), or "digital" ("2:05:00").
+/// - fractional-digits (int, none): The number of fraction digits of the seconds (0-9, larger values are an error) if subseconds are displayed as part of them (e.g. in the "digital" style). `none` (default) shows as many digits as needed.
+#let fmt-duration(value, locale: "en", style: "short", fractional-digits: none) = {
+  assert(type(locale) == str)
+
+  let spec = if type(value) == duration {
+    let seconds = int(value.seconds())
+    let sign = if seconds < 0 { -1 } else { 1 }
+    let seconds = calc.abs(seconds)
+    (
+      hours: sign * calc.quo(seconds, 3600),
+      minutes: sign * calc.rem(calc.quo(seconds, 60), 60),
+      seconds: sign * calc.rem(seconds, 60),
+    )
+  } else {
+    assert(type(value) == dictionary, message: "Expected a dictionary or a duration")
+    value
+  }
+
+  let opts = (
+    style: style,
+    fractional-digits: fractional-digits,
+  )
+  str(plug.format_duration(cbor.encode(spec), bytes(locale), cbor.encode(opts)))
+}
+
+/// Formats a number with locale specific digits, separators, and grouping.
+///
+/// - value (int, float, decimal, str): The number to format. Strings are parsed as decimals (e.g. "-1234.50").
+/// - locale (str): A Unicode Locale Identifier (see https://unicode.org/reports/tr35/tr35.html#Unicode_locale_identifier)
+/// - grouping (str, none): When to group the integer digits. "auto", "never", "always", "min2", or `none` (default, implies "auto").
+/// - min-fraction-digits (int, none): The minimum number of fraction digits (at most `max-fraction-digits`). The number is padded with zeros if necessary.
+/// - max-fraction-digits (int, none): The maximum number of fraction digits (not negative). The number is rounded with `rounding-mode` if necessary.
+/// - sign-display (str, none): When to display the sign. "auto", "never", "always", "except-zero", "negative", or `none` (default, implies "auto").
+/// - rounding-mode (str, none): How to round to `max-fraction-digits`. "ceil", "floor", "expand", "trunc", "half-ceil", "half-floor", "half-expand", "half-trunc", "half-even", or `none` (default, implies "half-expand").
+#let fmt-number(
+  value,
+  locale: "en",
+  grouping: none,
+  min-fraction-digits: none,
+  max-fraction-digits: none,
+  sign-display: none,
+  rounding-mode: none,
+) = {
+  assert(type(locale) == str)
+
+  if type(value) == decimal {
+    // Typst uses U+2212 MINUS SIGN for negative numbers
+    value = str(value).replace("\u{2212}", "-")
+  }
+
+  let opts = (
+    grouping: grouping,
+    min-fraction-digits: min-fraction-digits,
+    max-fraction-digits: max-fraction-digits,
+    sign-display: sign-display,
+    rounding-mode: rounding-mode,
+  )
+  str(plug.format_number(cbor.encode(value), bytes(locale), cbor.encode(opts)))
+}
+
+/// Joins a list of strings with locale specific separators and conjunctions, as in "A, B, and C".
+///
+/// - items (array): The strings to join.
+/// - locale (str): A Unicode Locale Identifier (see https://unicode.org/reports/tr35/tr35.html#Unicode_locale_identifier)
+/// - kind (str): "and" (default) for conjunctions, "or" for disjunctions, or "unit" for lists of measurements ("5 pounds, 12 ounces").
+/// - width (str, none): "wide", "short", "narrow", or `none` (default, implies "wide").
+#let fmt-list(items, locale: "en", kind: "and", width: none) = {
+  assert(type(locale) == str)
+  assert(type(items) == array)
+
+  let opts = (
+    kind: kind,
+    width: width,
+  )
+  str(plug.format_list(cbor.encode(items.map(str)), bytes(locale), cbor.encode(opts)))
+}
+
+/// Sorts strings in the order expected by readers of the `locale`.
+///
+/// - items (array): The items to sort.
+/// - key (function, none): If set, the items are sorted by the string this function returns for each item.
+/// - locale (str): A Unicode Locale Identifier (see https://unicode.org/reports/tr35/tr35.html#Unicode_locale_identifier). The collation can be selected with the `co` extension (e.g. "de-u-co-phonebk").
+/// - strength (str, none): Which differences are significant. "primary" (base letters), "secondary" (accents), "tertiary" (case), "quaternary", "identical", or `none` (default, implies "tertiary" for most locales).
+/// - case-first (str, none): Whether uppercase or lowercase letters sort first. "upper", "lower", "off", or `none` (default, locale specific).
+/// - numeric (bool, none): If `true`, sequences of digits are compared by their numeric value ("2" < "10").
+/// - alternate-handling (str, none): "non-ignorable" or "shifted" (ignore spaces and punctuation), or `none` (default, locale specific).
+/// - indices (bool): If `true`, returns the indices of the sorted items instead of the items.
+#let sort(
+  items,
+  key: none,
+  locale: "en",
+  strength: none,
+  case-first: none,
+  numeric: none,
+  alternate-handling: none,
+  indices: false,
+) = {
+  assert(type(locale) == str)
+  assert(type(items) == array)
+
+  let keys = if key == none { items } else { items.map(key) }
+  let opts = (
+    strength: strength,
+    case-first: case-first,
+    numeric: numeric,
+    alternate-handling: alternate-handling,
+    indices: indices or key != none,
+  )
+  let sorted = cbor(plug.sort(cbor.encode(keys.map(str)), bytes(locale), cbor.encode(opts)))
+  if key == none or indices {
+    sorted
+  } else {
+    sorted.map(i => items.at(i))
+  }
 }
 
 /// Gets information about ICU4X' understanding of the `locale`
 ///
+/// Returns the subtags and extensions of the locale (the keywords of the Unicode extension are a dictionary from the key to its subtags) and the resolved `preferences`: `region`, `hour-cycle`, `calendar`, `first-day`, `numbering-system`, and `measurement-system`.
+///
+/// `locale`: A Unicode Locale Identifier (see https://unicode.org/reports/tr35/tr35.html#Unicode_locale_identifier)
+/// `transform`: How to transform the locale before returning it. "canonicalize" (resolve aliases such as "iw" to "he"), "maximize" (add likely subtags such as "zh-TW" to "zh-Hant-TW"), "minimize" (remove likely subtags), or `none` (default, return the locale as is). Aliases are resolved for all transformations.
+#let locale-info(locale, transform: none) = {
+  assert(type(locale) == str)
+
+  let info = if transform == none {
+    plug.locale_info
+  } else if transform == "canonicalize" {
+    plug.locale_canonicalize
+  } else if transform == "maximize" {
+    plug.locale_maximize
+  } else if transform == "minimize" {
+    plug.locale_minimize
+  } else {
+    panic("Invalid transform - expected \"canonicalize\", \"maximize\", \"minimize\", or none - got " + repr(transform))
+  }
+  cbor(info(bytes(locale)))
+}
+
+/// Gets the locales that the date/time data for `locale` is searched in (the fallback chain) and which of them have data.
+///
+/// Returns a dictionary with the `requested` locale, the locale the data is `resolved` from, whether the data is `exact` (i.e. `requested == resolved` or both are equivalent by the likely subtags, e.g. "en" for "en-US"), and the `chain` of locales with `locale` and `has-data`.
+///
 /// `locale`: A Unicode Locale Identifier (see https://unicode.org/reports/tr35/tr35.html#Unicode_locale_identifier)
-#let locale-info(locale) = {
+#let locale-fallback(locale) = {
+  assert(type(locale) == str)
+
+  cbor(plug.locale_fallback(bytes(locale)))
+}
+
+/// Gets the week data of a `locale` and (optionally) the week numbers of a date.
+///
+/// Returns a dictionary with the `first-day` of the week, the `weekend` days ("mon", "tue", ..., "sun"), the minimal number of days in the first week of a year (`min-days`), and `week`. If `dt` is specified, `week` is a dictionary with the week numbers under `iso` (ISO 8601) and `locale` rules, each with `week-of-year`, `week-year`, and `week-of-month`. Otherwise, it's `none`.
+///
+/// - dt (dictionary, datetime, str, none): The date to get the week numbers of (like `dt` in `fmt`).
+/// - locale (str): A Unicode Locale Identifier (see https://unicode.org/reports/tr35/tr35.html#Unicode_locale_identifier)
+#let week-info(dt: none, locale: "en") = {
+  assert(type(locale) == str)
+
+  let spec = if dt != none { datetime-to-dict(dt) }
+  cbor(plug.week_info(bytes(locale), cbor.encode(spec)))
+}
+
+/// Gets the localized names of the months, weekdays, eras, and day periods (e.g. for table headers).
+///
+/// Returns a dictionary with `months`, `weekdays`, `eras`, and `day-periods`.
+/// - `months` has the `year` that the months are from, the month `codes` (e.g. "M05L" for a leap month), and the names in the `format` (used in dates, e.g. the genitive in some languages) and `standalone` (e.g. for headings) contexts. Each context has arrays with the `wide`, `abbreviated`, and `narrow` names.
+/// - `weekdays` has the names from Monday to Sunday in the `format` and `standalone` contexts. Each context has arrays with the `wide`, `abbreviated`, `short`, and `narrow` names.
+/// - `eras` is an array of the eras of the calendar (oldest first) with their `code` and `wide`, `abbreviated`, and `narrow` names. It's empty for calendars without eras.
+/// - `day-periods` has the `wide`, `abbreviated`, and `narrow` names of `am`, `pm`, `noon`, and `midnight`. Locales without names for noon and midnight use the names of AM and PM.
+///
+/// - locale (str): A Unicode Locale Identifier (see https://unicode.org/reports/tr35/tr35.html#Unicode_locale_identifier)
+/// - calendar (str, none): The calendar to get the names of (like `calendar` in `fmt`). `none` (default) implies the calendar of `locale`.
+/// - year (int, none): The year (in `calendar`) to get the months of. This matters for calendars with leap months. `none` (default) implies the year of January 1st, 2024.
+#let date-symbols(locale: "en", calendar: none, year: none) = {
+  assert(type(locale) == str)
+
+  cbor(plug.date_symbols(bytes(locale), cbor.encode(calendar), cbor.encode(year)))
+}
+
+/// Creates the grid of a month for a calendar (weeks × days).
+///
+/// Weeks start on the first day of `locale` (see `week-info`). Returns a dictionary with the localized `title` (e.g. "March 2025"), the `year`, `month`, and `month-code` in the calendar, the column `headers`, and the `weeks`. Each header has the `weekday` ("mon", "tue", ..., "sun"), its `narrow`, `short`, `abbreviated`, and `wide` name, and `is-weekend`. Each week has its `number` (by the rules of `locale`) and seven `days` with the `day` of the month, its localized `label`, `in-month` (`false` for days of the previous and next month), and the ISO `date` (a dictionary that can be passed to `fmt`).
+///
+/// - year (int): The year in `calendar` (in `era` if specified).
+/// - month (int, str): The month of the year (1-based) or a month code (e.g. "M05L" for a leap month).
+/// - locale (str): A Unicode Locale Identifier (see https://unicode.org/reports/tr35/tr35.html#Unicode_locale_identifier)
+/// - calendar (str, none): The calendar of the grid (like `calendar` in `fmt`). `none` (default) implies the calendar of `locale`.
+/// - era (str, none): The era of `year`.
+#let month-grid(year, month, locale: "en", calendar: none, era: none) = {
+  assert(type(locale) == str)
+
+  let spec = (year: year, era: era)
+  if type(month) == str {
+    spec.month-code = month
+  } else {
+    spec.month = month
+  }
+  cbor(plug.month_grid(cbor.encode(spec), bytes(locale), cbor.encode(calendar)))
+}
+
+/// Gets the plural category of a number in the `locale`.
+///
+/// Returns one of "zero", "one", "two", "few", "many", or "other".
+///
+/// - number (int, float, decimal, str): The number. Strings are parsed as decimals - trailing zeros are significant (e.g. "1.0").
+/// - locale (str): A Unicode Locale Identifier (see https://unicode.org/reports/tr35/tr35.html#Unicode_locale_identifier)
+/// - kind (str): "cardinal" (default) for quantities ("1 file", "2 files") or "ordinal" for ordering ("1st", "2nd").
+#let plural-category(number, locale: "en", kind: "cardinal") = {
   assert(type(locale) == str)
 
-  cbor(plug.locale_info(bytes(locale)))
+  // Typst uses U+2212 MINUS SIGN for negative numbers
+  let number = str(number).replace("\u{2212}", "-")
+  str(plug.plural_category(bytes(number), bytes(locale), cbor.encode(kind)))
+}
+
+/// Gets the localized names of a locale and its components (language, script, region, and variants).
+///
+/// - locale (str): The Unicode Locale Identifier to get the names of (see https://unicode.org/reports/tr35/tr35.html#Unicode_locale_identifier)
+/// - display-locale (str): The Unicode Locale Identifier of the language to display the names in.
+/// - style (str, none): "long", "short" (e.g. "UK" instead of "United Kingdom"), "narrow", "menu", or `none` (default, implies "long").
+/// - language-display (str, none): How to name the full locale. "dialect" (e.g. "British English") or "standard" (e.g. "English (United Kingdom)"), or `none` (default, implies "dialect").
+#let display-names(locale, display-locale: "en", style: none, language-display: none) = {
+  assert(type(locale) == str)
+  assert(type(display-locale) == str)
+
+  let opts = (
+    style: style,
+    language-display: language-display,
+  )
+  cbor(plug.locale_display_names(bytes(locale), bytes(display-locale), cbor.encode(opts)))
 }
+
+/// Converts a date to another calendar and gets its fields.
+///
+/// Returns a dictionary with `era`, `era-year`, `cyclic-year`, `related-iso`, `extended-year`, `month-code`, `ordinal-month`, `is-leap-month`, `day`, `day-of-week` (1 = Monday, 7 = Sunday), `day-of-year`, `days-in-month`, `days-in-year`, `months-in-year`, and `is-leap-year`. Fields that don't apply to the calendar are `none`.
+///
+/// - dt (dictionary, datetime, str): The date to convert (like the `dt` argument of `fmt`). It must have a date.
+/// - calendar (str): The calendar to convert to. "buddhist", "chinese", "coptic", "dangi", "ethioaa", "ethiopic", "gregory", "hebrew", "indian", "islamic-civil", "islamic-tbla", "islamic-umalqura", "iso8601", "japanese", "persian", or "roc".
+#let to-calendar(dt, calendar) = {
+  assert(type(calendar) == str)
+
+  cbor(plug.to_calendar(cbor.encode(datetime-to-dict(dt)), cbor.encode(calendar)))
+}
+
+/// Gets statistics about the formatter caches of the plugin.
+///
+/// Returns a dictionary with `formatters` (used by `fmt`, `fmt-batch`, `fmt-parts`, and `fmt-range`) and `patterns` (used with `experimental-pattern`). Each has the number of `hits` and `misses`, the number of cached `entries`, and the `capacity` of the cache.
+///
+/// This is only meant for diagnostics: Typst caches the results of plugin calls and doesn't guarantee that all calls reach the same instance of the plugin. Use `fmt-batch` with `stats: true` for the statistics of one call.
+#let cache-stats() = cbor(plug.cache_stats())